@@ -319,7 +319,8 @@ fn update_character_animations(
                                 }
                             },
                             crate::client::terrain::ResourceNodeType::Rock |
-                            crate::client::terrain::ResourceNodeType::OreDeposit => {
+                            crate::client::terrain::ResourceNodeType::OreDeposit |
+                            crate::client::terrain::ResourceNodeType::EssenceMine => {
                                 // Mining animation - both arms swinging together
                                 if let Ok(mut body_transform) = transform_query.get_mut(model.body) {
                                     body_transform.rotation = Quat::from_rotation_x(0.3);
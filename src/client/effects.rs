@@ -1,18 +1,72 @@
 use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
 use crate::client::input::Player;
-use crate::systems::skills_system::GatheringInProgress;
+use crate::systems::skills_system::{GatheringInProgress, LevelUpEvent, MilestoneEvent};
+use crate::systems::cooking_system::Fire;
+use crate::systems::projectile_system::{Projectile, ProjectileKind};
 
 pub struct EffectsPlugin;
 
 impl Plugin for EffectsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (
-            update_gathering_effects,
-            update_effect_particles,
-        ));
+        app.init_resource::<LevelUpPopups>()
+           .add_systems(Startup, setup_projectile_trail_assets)
+           .add_systems(Update, (
+               update_gathering_effects,
+               update_effect_particles,
+               spawn_level_up_effects,
+               queue_milestone_popups,
+               update_firework_rockets,
+               show_level_up_popups,
+               attach_fire_visuals,
+               update_fire_effects,
+               attach_projectile_visuals,
+               update_projectile_trails,
+           ));
     }
 }
 
+// Colors used for level-up firework bursts
+const FIREWORK_COLORS: [Color; 4] = [
+    Color::rgb(1.0, 0.85, 0.2),
+    Color::rgb(1.0, 0.3, 0.3),
+    Color::rgb(0.3, 0.8, 1.0),
+    Color::rgb(0.6, 1.0, 0.4),
+];
+
+// Time between puffs of particles behind a projectile
+const TRAIL_INTERVAL: f32 = 0.05;
+
+// A single level-up message shown on screen
+pub struct LevelUpPopup {
+    pub skill_name: String,
+    pub old_level: u32,
+    pub new_level: u32,
+    pub timer: Timer,
+}
+
+// An achievement or broadcast message shown on screen
+pub struct MilestonePopup {
+    pub description: String,
+    pub broadcast: bool,
+    pub timer: Timer,
+}
+
+// Level-up and milestone popups currently on screen
+#[derive(Resource, Default)]
+pub struct LevelUpPopups {
+    pub active: Vec<LevelUpPopup>,
+    pub milestones: Vec<MilestonePopup>,
+}
+
+// Component for a firework rocket that bursts into particles when its fuse runs out
+#[derive(Component)]
+pub struct FireworkRocket {
+    pub fuse: Timer,
+    pub velocity: Vec3,
+    pub color: Color,
+}
+
 // Component for particle effects
 #[derive(Component)]
 pub struct ParticleEffect {
@@ -51,7 +105,8 @@ fn update_gathering_effects(
                         );
                     },
                     crate::client::terrain::ResourceNodeType::Rock |
-                    crate::client::terrain::ResourceNodeType::OreDeposit => {
+                    crate::client::terrain::ResourceNodeType::OreDeposit |
+                    crate::client::terrain::ResourceNodeType::EssenceMine => {
                         // Rock dust effect
                         spawn_particles(
                             &mut commands,
@@ -106,6 +161,22 @@ fn spawn_particles(
         ..default()
     });
 
+    spawn_particles_with(commands, &mesh, &material, position, count, lifetime, speed, 0.7);
+}
+
+// Helper function to spawn particles from an existing mesh and material.
+// Particles fade by changing their material, so pass a fade_start of 0.0 if it's shared.
+#[allow(clippy::too_many_arguments)]
+fn spawn_particles_with(
+    commands: &mut Commands,
+    mesh: &Handle<Mesh>,
+    material: &Handle<StandardMaterial>,
+    position: Vec3,
+    count: usize,
+    lifetime: f32,
+    speed: f32,
+    fade_start: f32,
+) {
     for _ in 0..count {
         // Random direction
         let direction = Vec3::new(
@@ -130,7 +201,7 @@ fn spawn_particles(
             ParticleEffect {
                 lifetime: Timer::from_seconds(particle_lifetime, TimerMode::Once),
                 velocity: direction * particle_speed,
-                fade_start: 0.7,
+                fade_start,
                 size_change: -0.5, // Shrink over time
             },
         ));
@@ -177,3 +248,393 @@ fn update_effect_particles(
         }
     }
 }
+
+// System to queue a popup and launch fireworks when a skill levels up
+fn spawn_level_up_effects(
+    mut commands: Commands,
+    mut events: EventReader<LevelUpEvent>,
+    mut popups: ResMut<LevelUpPopups>,
+    player_query: Query<&Transform, With<Player>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for event in events.read() {
+        popups.active.push(LevelUpPopup {
+            skill_name: event.skill_name.clone(),
+            old_level: event.old_level,
+            new_level: event.new_level,
+            timer: Timer::from_seconds(4.0, TimerMode::Once),
+        });
+
+        if let Ok(player_transform) = player_query.get_single() {
+            let rocket_mesh = meshes.add(Mesh::from(shape::Cube { size: 0.08 }));
+
+            for (index, color) in FIREWORK_COLORS.iter().enumerate() {
+                // Spread rockets around the player and stagger their fuses
+                let angle = index as f32 / FIREWORK_COLORS.len() as f32 * std::f32::consts::TAU;
+                let spread = Vec3::new(angle.cos(), 0.0, angle.sin()) * 0.6;
+
+                commands.spawn((
+                    PbrBundle {
+                        mesh: rocket_mesh.clone(),
+                        material: materials.add(StandardMaterial {
+                            base_color: *color,
+                            emissive: *color,
+                            ..default()
+                        }),
+                        transform: Transform::from_translation(player_transform.translation + Vec3::new(0.0, 1.0, 0.0)),
+                        ..default()
+                    },
+                    FireworkRocket {
+                        fuse: Timer::from_seconds(0.6 + index as f32 * 0.15, TimerMode::Once),
+                        velocity: Vec3::new(0.0, 4.0, 0.0) + spread,
+                        color: *color,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+// System to fly firework rockets upward and burst them into particles
+fn update_firework_rockets(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut FireworkRocket, &mut Transform)>,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, mut rocket, mut transform) in query.iter_mut() {
+        rocket.fuse.tick(time.delta());
+        transform.translation += rocket.velocity * time.delta_seconds();
+
+        if rocket.fuse.finished() {
+            spawn_particles(
+                &mut commands,
+                &mut meshes,
+                &mut materials,
+                transform.translation,
+                24, // Big burst
+                rocket.color,
+                0.06, // Medium particles
+                1.2, // Long lifetime
+                3.0, // Fast spread
+            );
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// System to queue a popup for each achievement, or a broadcast for the notable ones
+fn queue_milestone_popups(
+    mut events: EventReader<MilestoneEvent>,
+    mut popups: ResMut<LevelUpPopups>,
+) {
+    for event in events.read() {
+        popups.milestones.push(MilestonePopup {
+            description: event.milestone.description(),
+            broadcast: event.broadcast,
+            // Broadcasts stay up a little longer
+            timer: Timer::from_seconds(if event.broadcast { 6.0 } else { 4.0 }, TimerMode::Once),
+        });
+    }
+}
+
+// System to draw level-up and milestone popups at the top of the screen
+fn show_level_up_popups(
+    mut contexts: EguiContexts,
+    mut popups: ResMut<LevelUpPopups>,
+    time: Res<Time>,
+) {
+    for popup in popups.active.iter_mut() {
+        popup.timer.tick(time.delta());
+    }
+    for popup in popups.milestones.iter_mut() {
+        popup.timer.tick(time.delta());
+    }
+    popups.active.retain(|popup| !popup.timer.finished());
+    popups.milestones.retain(|popup| !popup.timer.finished());
+
+    if popups.active.is_empty() && popups.milestones.is_empty() {
+        return;
+    }
+
+    egui::Area::new("level_up_popups")
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+        .show(contexts.ctx_mut(), |ui| {
+            for popup in popups.active.iter() {
+                // Fade out over the last second
+                let alpha = (popup.timer.remaining_secs().min(1.0) * 255.0) as u8;
+
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(30, 30, 30, alpha / 4 * 3))
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255, 215, 0, alpha)))
+                    .rounding(egui::Rounding::same(4.0))
+                    .inner_margin(egui::style::Margin::same(8.0))
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new("Congratulations!")
+                            .strong()
+                            .size(18.0)
+                            .color(egui::Color32::from_rgba_unmultiplied(255, 215, 0, alpha)));
+                        // Big XP drops can skip levels, so show where the skill came from
+                        let message = if popup.new_level > popup.old_level + 1 {
+                            format!("You've just advanced {} {} levels! Level {} → {}.",
+                                popup.new_level - popup.old_level, capitalize(&popup.skill_name), popup.old_level, popup.new_level)
+                        } else {
+                            format!("You've just advanced a {} level! You are now level {}.",
+                                capitalize(&popup.skill_name), popup.new_level)
+                        };
+                        ui.label(egui::RichText::new(message)
+                            .color(egui::Color32::from_rgba_unmultiplied(255, 255, 255, alpha)));
+                    });
+                ui.add_space(4.0);
+            }
+
+            for popup in popups.milestones.iter() {
+                let alpha = (popup.timer.remaining_secs().min(1.0) * 255.0) as u8;
+                // Broadcasts go out in news red, personal achievements in green
+                let (title, color) = if popup.broadcast {
+                    ("News", egui::Color32::from_rgba_unmultiplied(255, 90, 60, alpha))
+                } else {
+                    ("Achievement unlocked", egui::Color32::from_rgba_unmultiplied(120, 230, 120, alpha))
+                };
+
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_unmultiplied(30, 30, 30, alpha / 4 * 3))
+                    .stroke(egui::Stroke::new(1.0, color))
+                    .rounding(egui::Rounding::same(4.0))
+                    .inner_margin(egui::style::Margin::same(8.0))
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(title).strong().size(16.0).color(color));
+                        ui.label(egui::RichText::new(&popup.description)
+                            .color(egui::Color32::from_rgba_unmultiplied(255, 255, 255, alpha)));
+                    });
+                ui.add_space(4.0);
+            }
+        });
+}
+
+// Capitalize the first letter of a skill name for display
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+// System to give newly lit fires a log pile, flame and light
+fn attach_fire_visuals(
+    mut commands: Commands,
+    query: Query<Entity, Added<Fire>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for entity in query.iter() {
+        let log_mesh = meshes.add(Mesh::from(shape::Box::new(0.6, 0.1, 0.1)));
+        let log_material = materials.add(Color::rgb(0.4, 0.25, 0.1).into());
+        let flame_material = materials.add(StandardMaterial {
+            base_color: Color::rgb(1.0, 0.5, 0.1),
+            emissive: Color::rgb(1.0, 0.4, 0.05),
+            ..default()
+        });
+
+        commands.entity(entity).with_children(|parent| {
+            // Two crossed logs
+            parent.spawn(PbrBundle {
+                mesh: log_mesh.clone(),
+                material: log_material.clone(),
+                transform: Transform::from_xyz(0.0, 0.05, 0.0).with_rotation(Quat::from_rotation_y(0.6)),
+                ..default()
+            });
+            parent.spawn(PbrBundle {
+                mesh: log_mesh,
+                material: log_material,
+                transform: Transform::from_xyz(0.0, 0.05, 0.0).with_rotation(Quat::from_rotation_y(-0.6)),
+                ..default()
+            });
+
+            // Flame
+            parent.spawn(PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::UVSphere {
+                    radius: 0.2,
+                    sectors: 8,
+                    stacks: 8,
+                })),
+                material: flame_material,
+                transform: Transform::from_xyz(0.0, 0.25, 0.0).with_scale(Vec3::new(1.0, 1.6, 1.0)),
+                ..default()
+            });
+
+            // Warm glow
+            parent.spawn(PointLightBundle {
+                point_light: PointLight {
+                    color: Color::rgb(1.0, 0.6, 0.2),
+                    intensity: 300.0,
+                    range: 6.0,
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.5, 0.0),
+                ..default()
+            });
+        });
+    }
+}
+
+// System to spawn embers rising from burning fires
+fn update_fire_effects(
+    mut commands: Commands,
+    query: Query<&Transform, With<Fire>>,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Only spawn embers occasionally
+    if (time.elapsed_seconds() * 4.0).sin() <= 0.95 {
+        return;
+    }
+
+    for transform in query.iter() {
+        spawn_particles(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            transform.translation + Vec3::new(0.0, 0.3, 0.0),
+            3, // A few embers
+            Color::rgb(1.0, 0.5, 0.1), // Orange
+            0.03, // Tiny particles
+            0.8, // Short lifetime
+            1.0, // Slow rise
+        );
+    }
+}
+
+// Meshes and materials shared by the trails of every projectile
+#[derive(Resource)]
+pub struct ProjectileTrailAssets {
+    pub arrow_mesh: Handle<Mesh>,
+    pub arrow_material: Handle<StandardMaterial>,
+    pub orb_mesh: Handle<Mesh>,
+}
+
+// Component for the particles a projectile leaves behind, all sharing one mesh and material
+#[derive(Component)]
+pub struct ProjectileTrail {
+    pub timer: Timer,
+    pub count: usize,
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+}
+
+// Material for trail particles; it's shared, so they shrink away rather than fade
+fn trail_material(color: Color) -> StandardMaterial {
+    StandardMaterial {
+        base_color: color,
+        emissive: color * 0.5,
+        ..default()
+    }
+}
+
+// Create the trail meshes and arrow material once rather than for every particle
+fn setup_projectile_trail_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ProjectileTrailAssets {
+        arrow_mesh: meshes.add(Mesh::from(shape::Cube { size: 0.02 })),
+        arrow_material: materials.add(trail_material(Color::rgb(0.85, 0.85, 0.85))),
+        orb_mesh: meshes.add(Mesh::from(shape::Cube { size: 0.05 })),
+    });
+}
+
+// System to give new projectiles an arrow shaft or a glowing spell orb
+fn attach_projectile_visuals(
+    mut commands: Commands,
+    query: Query<(Entity, &Projectile), Added<Projectile>>,
+    trail_assets: Res<ProjectileTrailAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, projectile) in query.iter() {
+        // Arrows all share a trail; each spell orb's trail takes on its color
+        let (count, mesh, material) = match projectile.kind {
+            ProjectileKind::Arrow => (1, trail_assets.arrow_mesh.clone(), trail_assets.arrow_material.clone()),
+            ProjectileKind::SpellOrb { color } => (2, trail_assets.orb_mesh.clone(), materials.add(trail_material(color))),
+        };
+        commands.entity(entity).insert(ProjectileTrail {
+            timer: Timer::from_seconds(TRAIL_INTERVAL, TimerMode::Repeating),
+            count,
+            mesh,
+            material,
+        });
+
+        commands.entity(entity).with_children(|parent| {
+            match projectile.kind {
+                ProjectileKind::Arrow => {
+                    // Shaft pointing along the direction of flight, with a grey head
+                    parent.spawn(PbrBundle {
+                        mesh: meshes.add(Mesh::from(shape::Box::new(0.04, 0.04, 0.5))),
+                        material: materials.add(Color::rgb(0.55, 0.4, 0.2).into()),
+                        ..default()
+                    });
+                    parent.spawn(PbrBundle {
+                        mesh: meshes.add(Mesh::from(shape::Box::new(0.07, 0.07, 0.1))),
+                        material: materials.add(Color::rgb(0.6, 0.6, 0.65).into()),
+                        transform: Transform::from_xyz(0.0, 0.0, -0.28),
+                        ..default()
+                    });
+                },
+                ProjectileKind::SpellOrb { color } => {
+                    parent.spawn(PbrBundle {
+                        mesh: meshes.add(Mesh::from(shape::UVSphere {
+                            radius: 0.15,
+                            sectors: 8,
+                            stacks: 8,
+                        })),
+                        material: materials.add(StandardMaterial {
+                            base_color: color,
+                            emissive: color,
+                            ..default()
+                        }),
+                        ..default()
+                    });
+                    parent.spawn(PointLightBundle {
+                        point_light: PointLight {
+                            color,
+                            intensity: 150.0,
+                            range: 3.0,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                },
+            }
+        });
+    }
+}
+
+// System to leave a trail of particles behind projectiles in flight
+fn update_projectile_trails(
+    mut commands: Commands,
+    mut query: Query<(&Transform, &mut ProjectileTrail)>,
+    time: Res<Time>,
+) {
+    for (transform, mut trail) in query.iter_mut() {
+        trail.timer.tick(time.delta());
+        if !trail.timer.just_finished() {
+            continue;
+        }
+
+        spawn_particles_with(
+            &mut commands,
+            &trail.mesh,
+            &trail.material,
+            transform.translation,
+            trail.count,
+            0.4, // Short-lived
+            0.2, // Barely drifting
+            0.0, // Shared material, so no fading
+        );
+    }
+}
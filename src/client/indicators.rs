@@ -1,6 +1,12 @@
 use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::client::camera::MainCamera;
 use crate::client::input::Player;
 use crate::client::terrain::ResourceNodeType;
+use crate::shared::components::Skills;
+use crate::shared::entities::NPC;
+use crate::systems::combat::combat_level;
+use crate::systems::resources::{DepletedResource, ResourceDatabase, ResourceNode};
 
 pub struct IndicatorsPlugin;
 
@@ -9,6 +15,7 @@ impl Plugin for IndicatorsPlugin {
         app.add_systems(Update, (
             update_resource_indicators,
             update_interaction_prompts,
+            draw_nameplates,
         ));
     }
 }
@@ -27,12 +34,17 @@ pub struct InteractionPrompt {
     pub timer: Timer,
 }
 
+// Nameplates float this far above their owner, and only show within range of the camera
+const NAMEPLATE_OFFSET: Vec3 = Vec3::new(0.0, 2.2, 0.0);
+const NAMEPLATE_RANGE: f32 = 30.0;
+
 // System to create and update resource indicators
 fn update_resource_indicators(
     mut commands: Commands,
     player_query: Query<&Transform, With<Player>>,
-    resource_query: Query<(Entity, &Transform, &ResourceNodeType), Without<Player>>,
+    resource_query: Query<(Entity, &Transform, &ResourceNodeType, Option<&ResourceNode>), (Without<Player>, Without<DepletedResource>)>,
     indicator_query: Query<(Entity, &ResourceIndicator)>,
+    resource_database: Res<ResourceDatabase>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
@@ -41,8 +53,15 @@ fn update_resource_indicators(
         // Define interaction range
         let interaction_range = 3.0;
 
+        // Remove indicators for nodes that have been depleted
+        for (indicator_entity, indicator) in indicator_query.iter() {
+            if !resource_query.contains(indicator.target) {
+                commands.entity(indicator_entity).despawn();
+            }
+        }
+
         // Check each resource
-        for (resource_entity, resource_transform, resource_type) in resource_query.iter() {
+        for (resource_entity, resource_transform, resource_type, resource_node) in resource_query.iter() {
             let distance = player_transform.translation.distance(resource_transform.translation);
 
             // Check if this resource already has an indicator
@@ -56,6 +75,7 @@ fn update_resource_indicators(
                     ResourceNodeType::Rock => Color::rgb(0.7, 0.7, 0.7),        // Gray for rocks
                     ResourceNodeType::OreDeposit => Color::rgb(0.8, 0.6, 0.2),  // Gold for ore
                     ResourceNodeType::FishingSpot => Color::rgb(0.2, 0.6, 0.9), // Blue for fishing
+                    ResourceNodeType::EssenceMine => Color::rgb(0.7, 0.5, 0.9), // Purple for essence
                 };
 
                 // Create a ring mesh
@@ -90,7 +110,12 @@ fn update_resource_indicators(
                     },
                 ));
 
-                // Also spawn a text prompt
+                // Also spawn a text prompt naming the resource tier and its level requirement
+                let prompt_text = match resource_node.and_then(|node| resource_database.resources.get(&node.definition_id)) {
+                    Some(definition) => format!("Press F to gather: {} (level {})", definition.name, definition.level_requirement),
+                    None => "Press F to gather".to_string(),
+                };
+
                 commands.spawn((
                     InteractionPrompt {
                         target: resource_entity,
@@ -100,7 +125,7 @@ fn update_resource_indicators(
                     // Use a text bundle for the prompt
                     Text2dBundle {
                         text: Text::from_section(
-                            "Press F to gather",
+                            prompt_text,
                             TextStyle {
                                 font_size: 20.0,
                                 color: Color::WHITE,
@@ -130,7 +155,7 @@ fn update_resource_indicators(
 fn update_interaction_prompts(
     mut commands: Commands,
     mut prompt_query: Query<(Entity, &mut InteractionPrompt, &mut Transform, &mut Text)>,
-    resource_query: Query<&Transform, Without<InteractionPrompt>>,
+    resource_query: Query<&Transform, (Without<InteractionPrompt>, Without<DepletedResource>)>,
     time: Res<Time>,
 ) {
     for (entity, mut prompt, mut transform, mut text) in prompt_query.iter_mut() {
@@ -154,8 +179,55 @@ fn update_interaction_prompts(
             // Make text face camera by setting rotation to identity
             transform.rotation = Quat::IDENTITY;
         } else {
-            // If resource no longer exists, remove the prompt
+            // If resource no longer exists (or is depleted), remove the prompt
             commands.entity(entity).despawn();
         }
     }
 }
+
+// Draw each NPC's and the player's name and combat level above their head, projected from the 3D camera
+#[allow(clippy::type_complexity)]
+fn draw_nameplates(
+    mut contexts: EguiContexts,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    target_query: Query<(&GlobalTransform, Option<&NPC>, Option<&Skills>), Or<(With<NPC>, With<Player>)>>,
+    player_query: Query<&Skills, With<Player>>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let player_level = player_query.get_single().map(combat_level).ok();
+    let painter = contexts.ctx_mut().layer_painter(egui::LayerId::background());
+    let font = egui::FontId::proportional(14.0);
+
+    for (target_transform, npc, skills) in target_query.iter() {
+        let position = target_transform.translation();
+        if position.distance(camera_transform.translation()) > NAMEPLATE_RANGE {
+            continue;
+        }
+        // Nothing to draw for anyone behind the camera
+        let Some(screen_position) = camera.world_to_viewport(camera_transform, position + NAMEPLATE_OFFSET) else {
+            continue;
+        };
+
+        // NPC levels are coloured by how they compare to the player: green below, yellow level, red above
+        let (label, color) = match (npc, skills) {
+            (Some(npc), _) => {
+                let color = match player_level.map(|player_level| npc.level.cmp(&player_level)) {
+                    Some(std::cmp::Ordering::Less) => egui::Color32::from_rgb(100, 255, 100),
+                    Some(std::cmp::Ordering::Equal) => egui::Color32::from_rgb(255, 255, 80),
+                    Some(std::cmp::Ordering::Greater) => egui::Color32::from_rgb(255, 90, 90),
+                    None => egui::Color32::WHITE,
+                };
+                (format!("{} (level-{})", npc.name, npc.level), color)
+            },
+            (None, Some(skills)) => (format!("You (level-{})", combat_level(skills)), egui::Color32::WHITE),
+            (None, None) => continue,
+        };
+
+        // A dark shadow keeps the text readable over bright terrain
+        let anchor = egui::pos2(screen_position.x, screen_position.y);
+        painter.text(anchor + egui::vec2(1.0, 1.0), egui::Align2::CENTER_BOTTOM, &label, font.clone(), egui::Color32::BLACK);
+        painter.text(anchor, egui::Align2::CENTER_BOTTOM, label, font.clone(), color);
+    }
+}
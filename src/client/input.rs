@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::client::physics::{Acceleration, Velocity, OnGround, JumpStrength};
+use crate::systems::thieving_system::Stunned;
 
 pub struct InputPlugin;
 
@@ -45,10 +46,16 @@ impl Plugin for InputPlugin {
 fn handle_keyboard_input(
     keyboard_input: Res<Input<KeyCode>>,
     settings: Res<PlayerSettings>,
-    mut query: Query<(&Transform, &mut Acceleration, &OnGround), With<Player>>,
+    mut query: Query<(&Transform, &mut Acceleration, &OnGround, Option<&Stunned>), With<Player>>,
     _time: Res<Time>,
 ) {
-    if let Ok((transform, mut acceleration, on_ground)) = query.get_single_mut() {
+    if let Ok((transform, mut acceleration, on_ground, stunned)) = query.get_single_mut() {
+        // Stunned players can't move
+        if stunned.is_some() {
+            acceleration.linear = Vec3::ZERO;
+            return;
+        }
+
         let mut direction = Vec3::ZERO;
 
         // Get movement direction from keyboard input
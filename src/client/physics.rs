@@ -38,6 +38,12 @@ pub struct OnGround(pub bool);
 #[derive(Component, Debug)]
 pub struct JumpStrength(pub f32);
 
+// Top horizontal speed (entities without it use DEFAULT_MAX_SPEED)
+#[derive(Component, Debug)]
+pub struct MaxSpeed(pub f32);
+
+pub const DEFAULT_MAX_SPEED: f32 = 5.0;
+
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
@@ -66,10 +72,10 @@ fn apply_gravity(
 
 // Apply acceleration to velocity
 fn apply_acceleration(
-    mut query: Query<(&mut Velocity, &Acceleration)>,
+    mut query: Query<(&mut Velocity, &Acceleration, Option<&MaxSpeed>)>,
     time: Res<Time>,
 ) {
-    for (mut velocity, acceleration) in query.iter_mut() {
+    for (mut velocity, acceleration, max_speed) in query.iter_mut() {
         velocity.linear += acceleration.linear * time.delta_seconds();
 
         // Apply friction to horizontal movement when on ground
@@ -90,7 +96,7 @@ fn apply_acceleration(
         }
 
         // Clamp maximum velocity
-        let max_horizontal_speed = 5.0;
+        let max_horizontal_speed = max_speed.map_or(DEFAULT_MAX_SPEED, |max_speed| max_speed.0);
         let horizontal_speed = Vec2::new(velocity.linear.x, velocity.linear.z).length();
         if horizontal_speed > max_horizontal_speed {
             let scale = max_horizontal_speed / horizontal_speed;
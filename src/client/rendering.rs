@@ -1,8 +1,16 @@
 use bevy::prelude::*;
 use crate::client::input::Player;
-use crate::client::physics::{Velocity, Acceleration, Collider, ColliderShape, Gravity, OnGround, JumpStrength};
-use crate::shared::components::{Skills, Health};
+use crate::client::physics::{Velocity, Acceleration, Collider, ColliderShape, Gravity, OnGround, JumpStrength, MaxSpeed};
+use crate::shared::components::Health;
+use crate::systems::skills::new_skills;
+use crate::systems::skills_system::SkillMilestones;
+use crate::systems::herblore_system::SkillBoosts;
+use crate::systems::agility_system::{RunEnergy, RUN_SPEED};
 use crate::systems::combat_system::CombatState;
+use crate::systems::prayer_system::{ActivePrayers, PrayerPoints};
+use crate::systems::slayer_system::SlayerProgress;
+use crate::systems::equipment_system::Equipment;
+use crate::systems::magic_system::Spellbook;
 use crate::systems::inventory_system::Inventory;
 
 pub struct RenderingPlugin;
@@ -70,46 +78,35 @@ fn setup_scene(
             ..default()
         },
         Player,  // Add the Player component to enable movement
-        Velocity { linear: Vec3::ZERO, angular: 0.0 },
-        Acceleration { linear: Vec3::ZERO },
-        Collider {
-            radius: 0.5,
-            height: 1.0,
-            shape: ColliderShape::Capsule
-        },
-        Gravity(9.8),
-        OnGround(false),
-        JumpStrength(8.0),
-        Skills {
-            attack: 1,
-            defense: 1,
-            strength: 1,
-            hitpoints: 10,
-            ranged: 1,
-            prayer: 1,
-            magic: 1,
-            cooking: 1,
-            woodcutting: 1,
-            fletching: 1,
-            fishing: 1,
-            firemaking: 1,
-            crafting: 1,
-            smithing: 1,
-            mining: 1,
-            herblore: 1,
-            agility: 1,
-            thieving: 1,
-            slayer: 1,
-            farming: 1,
-            runecrafting: 1,
-        },
+        (
+            Velocity { linear: Vec3::ZERO, angular: 0.0 },
+            Acceleration { linear: Vec3::ZERO },
+            Collider {
+                radius: 0.5,
+                height: 1.0,
+                shape: ColliderShape::Capsule
+            },
+            Gravity(9.8),
+            OnGround(false),
+            JumpStrength(8.0),
+            MaxSpeed(RUN_SPEED),
+        ),
+        RunEnergy::default(),
+        new_skills(),
+        SkillMilestones::default(),
+        SkillBoosts::default(),
+        (PrayerPoints::default(), ActivePrayers::default()),
+        SlayerProgress::default(),
         Health {
             current: 100,
             maximum: 100,
         },
         CombatState::default(),
+        Equipment::default(),
+        Spellbook::default(),
         Inventory {
-            items: std::collections::HashMap::new(),
+            // Start with a bronze axe, bronze pickaxe, small fishing net, tinderbox, hammer and knife
+            items: std::collections::HashMap::from([(20, 1), (24, 1), (28, 1), (31, 1), (32, 1), (33, 1)]),
             capacity: 28, // Standard RuneScape inventory size
             gold: 0,
         },
@@ -3,12 +3,18 @@ use bevy::render::mesh::shape::{Plane, Cylinder, UVSphere};
 use noise::{NoiseFn, Perlin};
 use rand::Rng;
 use crate::client::physics::{Collider, ColliderShape};
+use crate::systems::resources::{DepletedResource, ResourceDatabase, ResourceNode, ResourceRespawnedEvent};
+use crate::systems::agility_system::{AgilityDatabase, AgilityObstacle, ObstacleKind};
 
 pub struct TerrainPlugin;
 
 impl Plugin for TerrainPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, generate_terrain);
+        app.add_systems(Startup, generate_terrain)
+           .add_systems(Update, (
+               show_depleted_resources,
+               show_respawned_resources,
+           ));
     }
 }
 
@@ -48,12 +54,87 @@ pub enum BiomeType {
 }
 
 // Resource node types
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
 pub enum ResourceNodeType {
     Tree,
     Rock,
     OreDeposit,
     FishingSpot,
+    EssenceMine,
+}
+
+// Interactive objects placed in the world (cooking ranges, furnaces, player-lit fires, ...)
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldObject {
+    Fire,
+    Range,
+    Furnace,
+    Anvil,
+    AgilityObstacle,
+    Stall,
+    FarmingPatch,
+    RuneAltar,
+    PrayerAltar,
+    Gravestone,
+}
+
+impl WorldObject {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WorldObject::Fire => "Fire",
+            WorldObject::Range => "Cooking range",
+            WorldObject::Furnace => "Furnace",
+            WorldObject::Anvil => "Anvil",
+            WorldObject::AgilityObstacle => "Agility obstacle",
+            WorldObject::Stall => "Market stall",
+            WorldObject::FarmingPatch => "Farming patch",
+            WorldObject::RuneAltar => "Runecrafting altar",
+            WorldObject::PrayerAltar => "Altar",
+            WorldObject::Gravestone => "Gravestone",
+        }
+    }
+}
+
+// How close the player has to stand to use a world object
+pub const WORLD_OBJECT_RANGE: f32 = 2.5;
+
+// Find the closest world object matching a filter within interaction range
+pub fn nearest_world_object<'a>(
+    position: Vec3,
+    objects: impl Iterator<Item = (Entity, &'a Transform, &'a WorldObject)>,
+    filter: impl Fn(&WorldObject) -> bool,
+) -> Option<(Entity, WorldObject)> {
+    let mut closest = None;
+    let mut closest_distance = WORLD_OBJECT_RANGE;
+
+    for (entity, transform, object) in objects {
+        let distance = position.distance(transform.translation);
+        if filter(object) && distance <= closest_distance {
+            closest_distance = distance;
+            closest = Some((entity, *object));
+        }
+    }
+
+    closest
+}
+
+// Mesh, material and position a resource node switches to while depleted
+pub struct DepletedVisual {
+    pub mesh: Handle<Mesh>,
+    pub material: Handle<StandardMaterial>,
+    pub translation: Vec3,
+}
+
+// Visual states of a resource node, swapped when it depletes and respawns
+#[derive(Component)]
+pub struct ResourceNodeVisuals {
+    pub full_mesh: Handle<Mesh>,
+    pub full_material: Handle<StandardMaterial>,
+    pub full_translation: Vec3,
+    // None hides the node completely while depleted
+    pub depleted: Option<DepletedVisual>,
+    // Extra child entity (tree leaves) hidden while depleted
+    pub foliage: Option<Entity>,
 }
 
 // Generate terrain with different biomes
@@ -61,6 +142,8 @@ fn generate_terrain(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    resource_database: Res<ResourceDatabase>,
+    agility_database: Res<AgilityDatabase>,
 ) {
     let settings = TerrainSettings::default();
     let perlin = Perlin::new(settings.seed);
@@ -86,44 +169,78 @@ fn generate_terrain(
         let noise_value = perlin.get([x as f64 * 0.05, z as f64 * 0.05]);
 
         if noise_value > 0.2 && noise_value < 0.8 {
+            // Pick a tree tier (normal, oak, willow, yew)
+            let Some(definition) = resource_database.random_definition(ResourceNodeType::Tree, &mut rng) else {
+                continue;
+            };
+
             let height = 1.5 + (noise_value as f32 * 1.5);
             let radius = 0.2 + (noise_value as f32 * 0.1);
 
             // Tree trunk
-            commands.spawn((
+            let trunk_mesh = meshes.add(Cylinder {
+                radius,
+                height,
+                resolution: 8,
+                segments: 1,
+            }.into());
+            let trunk_material = materials.add(Color::rgb(0.6, 0.4, 0.2).into());
+            let trunk_translation = Vec3::new(x, height/2.0, z);
+
+            // Stump left behind once the tree has been cut down
+            let stump_height = 0.3;
+            let stump_mesh = meshes.add(Cylinder {
+                radius: radius * 1.1,
+                height: stump_height,
+                resolution: 8,
+                segments: 1,
+            }.into());
+
+            let trunk = commands.spawn((
                 PbrBundle {
-                    mesh: meshes.add(Cylinder {
-                        radius,
-                        height,
-                        resolution: 8,
-                        segments: 1,
-                    }.into()),
-                    material: materials.add(Color::rgb(0.6, 0.4, 0.2).into()),
-                    transform: Transform::from_xyz(x, height/2.0, z),
+                    mesh: trunk_mesh.clone(),
+                    material: trunk_material.clone(),
+                    transform: Transform::from_translation(trunk_translation),
                     ..default()
                 },
                 ResourceNodeType::Tree,
+                ResourceNode { definition_id: definition.id },
                 Collider {
                     radius,
                     height,
                     shape: ColliderShape::Cylinder,
                 },
-            ));
+            )).id();
 
-            // Tree leaves
-            commands.spawn(PbrBundle {
+            // Tree leaves (child of the trunk so they can be hidden when it is cut down)
+            let leaves = commands.spawn(PbrBundle {
                 mesh: meshes.add(UVSphere {
                     radius: radius * 3.0,
                     sectors: 8,
                     stacks: 8,
                 }.into()),
-                material: materials.add(Color::rgb(0.2, 0.6, 0.2).into()),
-                transform: Transform::from_xyz(x, height + radius * 1.5, z),
+                material: materials.add(definition.color.into()),
+                transform: Transform::from_xyz(0.0, height/2.0 + radius * 1.5, 0.0),
                 ..default()
+            }).set_parent(trunk).id();
+
+            commands.entity(trunk).insert(ResourceNodeVisuals {
+                full_mesh: trunk_mesh,
+                full_material: trunk_material.clone(),
+                full_translation: trunk_translation,
+                depleted: Some(DepletedVisual {
+                    mesh: stump_mesh,
+                    material: trunk_material,
+                    translation: Vec3::new(x, stump_height/2.0, z),
+                }),
+                foliage: Some(leaves),
             });
         }
     }
 
+    // Shared look for rocks and ore deposits that have been mined out
+    let empty_rock_material = materials.add(Color::rgb(0.3, 0.28, 0.26).into());
+
     // Generate rocks
     for _ in 0..30 {
         let x = rng.gen_range(-settings.size/2.0..settings.size/2.0);
@@ -133,25 +250,33 @@ fn generate_terrain(
         let noise_value = perlin.get([x as f64 * 0.1 + 100.0, z as f64 * 0.1 + 100.0]);
 
         if noise_value > 0.5 {
+            let Some(definition) = resource_database.random_definition(ResourceNodeType::Rock, &mut rng) else {
+                continue;
+            };
+
             let size = 0.5 + (noise_value as f32 * 0.5);
+            let rock_mesh = meshes.add(UVSphere {
+                radius: size,
+                sectors: 8,
+                stacks: 8,
+            }.into());
+            let rock_material = materials.add(definition.color.into());
 
             commands.spawn((
                 PbrBundle {
-                    mesh: meshes.add(UVSphere {
-                        radius: size,
-                        sectors: 8,
-                        stacks: 8,
-                    }.into()),
-                    material: materials.add(Color::rgb(0.5, 0.5, 0.5).into()),
+                    mesh: rock_mesh.clone(),
+                    material: rock_material.clone(),
                     transform: Transform::from_xyz(x, size, z),
                     ..default()
                 },
                 ResourceNodeType::Rock,
+                ResourceNode { definition_id: definition.id },
                 Collider {
                     radius: size,
                     height: size * 2.0,
                     shape: ColliderShape::Sphere,
                 },
+                empty_rock_visuals(rock_mesh, rock_material, empty_rock_material.clone(), Vec3::new(x, size, z)),
             ));
         }
     }
@@ -163,18 +288,63 @@ fn generate_terrain(
 
         let size = rng.gen_range(0.3..0.7);
 
+        // Pick an ore tier (copper, tin, iron, coal, mithril)
+        let Some(definition) = resource_database.random_definition(ResourceNodeType::OreDeposit, &mut rng) else {
+            continue;
+        };
+
+        let ore_mesh = meshes.add(UVSphere {
+            radius: size,
+            sectors: 8,
+            stacks: 8,
+        }.into());
+        let ore_material = materials.add(definition.color.into());
+
+        commands.spawn((
+            PbrBundle {
+                mesh: ore_mesh.clone(),
+                material: ore_material.clone(),
+                transform: Transform::from_xyz(x, size, z),
+                ..default()
+            },
+            ResourceNodeType::OreDeposit,
+            ResourceNode { definition_id: definition.id },
+            Collider {
+                radius: size,
+                height: size * 2.0,
+                shape: ColliderShape::Sphere,
+            },
+            empty_rock_visuals(ore_mesh, ore_material, empty_rock_material.clone(), Vec3::new(x, size, z)),
+        ));
+    }
+
+    // Rune essence rocks in a cluster north of the village (they never run out)
+    for index in 0..4 {
+        let Some(definition) = resource_database.random_definition(ResourceNodeType::EssenceMine, &mut rng) else {
+            break;
+        };
+
+        let size = 0.8;
+        let angle = index as f32 * std::f32::consts::FRAC_PI_2;
+        let position = Vec3::new(-22.0 + angle.cos() * 2.5, size, 22.0 + angle.sin() * 2.5);
+
         commands.spawn((
             PbrBundle {
                 mesh: meshes.add(UVSphere {
                     radius: size,
-                    sectors: 8,
-                    stacks: 8,
+                    sectors: 10,
+                    stacks: 10,
                 }.into()),
-                material: materials.add(Color::rgb(0.6, 0.3, 0.1).into()),
-                transform: Transform::from_xyz(x, size, z),
+                material: materials.add(StandardMaterial {
+                    base_color: definition.color,
+                    emissive: Color::rgb(0.1, 0.05, 0.2),
+                    ..default()
+                }),
+                transform: Transform::from_translation(position),
                 ..default()
             },
-            ResourceNodeType::OreDeposit,
+            ResourceNodeType::EssenceMine,
+            ResourceNode { definition_id: definition.id },
             Collider {
                 radius: size,
                 height: size * 2.0,
@@ -188,17 +358,40 @@ fn generate_terrain(
         let x = rng.gen_range(-settings.size/2.0..settings.size/2.0);
         let z = rng.gen_range(-settings.size/2.0..settings.size/2.0);
 
+        // Pick a fishing spot tier (net, rod, cage)
+        let Some(definition) = resource_database.random_definition(ResourceNodeType::FishingSpot, &mut rng) else {
+            continue;
+        };
+
+        let spot_mesh = meshes.add(Plane { size: 1.0, subdivisions: 0 }.into());
+        let spot_material = materials.add(definition.color.into());
+
         commands.spawn((
             PbrBundle {
-                mesh: meshes.add(Plane { size: 1.0, subdivisions: 0 }.into()),
-                material: materials.add(Color::rgba(0.2, 0.4, 0.8, 0.7).into()),
+                mesh: spot_mesh.clone(),
+                material: spot_material.clone(),
                 transform: Transform::from_xyz(x, 0.01, z),
                 ..default()
             },
             ResourceNodeType::FishingSpot,
+            ResourceNode { definition_id: definition.id },
+            // Fishing spots disappear entirely while depleted
+            ResourceNodeVisuals {
+                full_mesh: spot_mesh,
+                full_material: spot_material,
+                full_translation: Vec3::new(x, 0.01, z),
+                depleted: None,
+                foliage: None,
+            },
         ));
     }
 
+    // Add a small village with a cooking range near the spawn point
+    spawn_village(&mut commands, &mut meshes, &mut materials);
+
+    // Lay out the agility course obstacles
+    spawn_agility_courses(&mut commands, &mut meshes, &mut materials, &agility_database);
+
     // Add some mountains
     for _ in 0..5 {
         let x = rng.gen_range(-settings.size/2.0..settings.size/2.0);
@@ -227,3 +420,221 @@ fn generate_terrain(
         ));
     }
 }
+
+// Spawn the village objects players use for processing skills
+fn spawn_village(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+) {
+    // Cooking range: an iron box with a glowing hob
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(1.2, 0.9, 0.8))),
+            material: materials.add(Color::rgb(0.25, 0.25, 0.28).into()),
+            transform: Transform::from_xyz(6.0, 0.45, 4.0),
+            ..default()
+        },
+        WorldObject::Range,
+        Collider {
+            radius: 0.6,
+            height: 0.9,
+            shape: ColliderShape::Box,
+        },
+    )).with_children(|parent| {
+        parent.spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(0.9, 0.05, 0.6))),
+            material: materials.add(StandardMaterial {
+                base_color: Color::rgb(0.9, 0.3, 0.1),
+                emissive: Color::rgb(0.8, 0.2, 0.05),
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, 0.47, 0.0),
+            ..default()
+        });
+    });
+
+    // Furnace: a squat stone kiln with a glowing mouth
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(1.6, 1.8, 1.4))),
+            material: materials.add(Color::rgb(0.45, 0.42, 0.4).into()),
+            transform: Transform::from_xyz(9.0, 0.9, 4.0),
+            ..default()
+        },
+        WorldObject::Furnace,
+        Collider {
+            radius: 0.8,
+            height: 1.8,
+            shape: ColliderShape::Box,
+        },
+    )).with_children(|parent| {
+        parent.spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(0.6, 0.5, 0.05))),
+            material: materials.add(StandardMaterial {
+                base_color: Color::rgb(1.0, 0.5, 0.1),
+                emissive: Color::rgb(1.0, 0.4, 0.05),
+                ..default()
+            }),
+            transform: Transform::from_xyz(0.0, -0.4, -0.71),
+            ..default()
+        });
+    });
+
+    // Anvil: a dark iron block on a wooden stump
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cylinder {
+                radius: 0.35,
+                height: 0.5,
+                ..default()
+            })),
+            material: materials.add(Color::rgb(0.4, 0.28, 0.15).into()),
+            transform: Transform::from_xyz(11.5, 0.25, 4.0),
+            ..default()
+        },
+        WorldObject::Anvil,
+        Collider {
+            radius: 0.5,
+            height: 0.8,
+            shape: ColliderShape::Cylinder,
+        },
+    )).with_children(|parent| {
+        parent.spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(0.9, 0.3, 0.4))),
+            material: materials.add(Color::rgb(0.2, 0.2, 0.22).into()),
+            transform: Transform::from_xyz(0.0, 0.4, 0.0),
+            ..default()
+        });
+    });
+}
+
+// Spawn every obstacle of every agility course. The obstacle entity sits at the start of
+// the obstacle (where the player interacts) and its visual spans to the end.
+fn spawn_agility_courses(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    agility_database: &AgilityDatabase,
+) {
+    let wood_material = materials.add(Color::rgb(0.55, 0.38, 0.2).into());
+    let rope_material = materials.add(Color::rgb(0.75, 0.65, 0.45).into());
+    let stone_material = materials.add(Color::rgb(0.55, 0.55, 0.5).into());
+    let pipe_material = materials.add(Color::rgb(0.3, 0.3, 0.32).into());
+
+    for (course_id, course) in agility_database.courses.iter().enumerate() {
+        for (index, obstacle) in course.obstacles.iter().enumerate() {
+            let span = obstacle.end - obstacle.start;
+            let length = span.length();
+            let midpoint = span / 2.0;
+            // Rotation that points local +Z along the obstacle
+            let facing = Quat::from_rotation_y(span.x.atan2(span.z));
+
+            let (mesh, material, transform) = match obstacle.kind {
+                ObstacleKind::LogBalance => (
+                    meshes.add(Mesh::from(Cylinder { radius: 0.2, height: length, resolution: 8, segments: 1 })),
+                    wood_material.clone(),
+                    Transform::from_translation(midpoint + Vec3::Y * 0.2)
+                        .with_rotation(facing * Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                ),
+                ObstacleKind::Net => (
+                    meshes.add(Mesh::from(shape::Box::new(2.0, 2.5, 0.1))),
+                    rope_material.clone(),
+                    Transform::from_translation(midpoint + Vec3::Y * 1.25).with_rotation(facing),
+                ),
+                ObstacleKind::Wall => (
+                    meshes.add(Mesh::from(shape::Box::new(2.5, 0.8, 0.4))),
+                    stone_material.clone(),
+                    Transform::from_translation(midpoint + Vec3::Y * 0.4).with_rotation(facing),
+                ),
+                ObstacleKind::Pipe => (
+                    meshes.add(Mesh::from(Cylinder { radius: 0.5, height: length, resolution: 12, segments: 1 })),
+                    pipe_material.clone(),
+                    Transform::from_translation(midpoint + Vec3::Y * 0.5)
+                        .with_rotation(facing * Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+                ),
+            };
+
+            commands.spawn((
+                SpatialBundle {
+                    transform: Transform::from_translation(obstacle.start),
+                    ..default()
+                },
+                WorldObject::AgilityObstacle,
+                AgilityObstacle { course_id, index },
+            )).with_children(|parent| {
+                parent.spawn(PbrBundle {
+                    mesh,
+                    material,
+                    transform,
+                    ..default()
+                });
+            });
+        }
+    }
+}
+
+// Visuals for a mined-out rock: the same shape, darker and sunk slightly into the ground
+fn empty_rock_visuals(
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+    empty_material: Handle<StandardMaterial>,
+    translation: Vec3,
+) -> ResourceNodeVisuals {
+    ResourceNodeVisuals {
+        full_mesh: mesh.clone(),
+        full_material: material,
+        full_translation: translation,
+        depleted: Some(DepletedVisual {
+            mesh,
+            material: empty_material,
+            translation: translation - Vec3::new(0.0, translation.y * 0.4, 0.0),
+        }),
+        foliage: None,
+    }
+}
+
+// Swap depleted nodes to their stump / empty rock look
+fn show_depleted_resources(
+    mut node_query: Query<(&ResourceNodeVisuals, &mut Handle<Mesh>, &mut Handle<StandardMaterial>, &mut Transform, &mut Visibility), Added<DepletedResource>>,
+    mut foliage_query: Query<&mut Visibility, Without<ResourceNodeVisuals>>,
+) {
+    for (visuals, mut mesh, mut material, mut transform, mut visibility) in node_query.iter_mut() {
+        match &visuals.depleted {
+            Some(depleted) => {
+                *mesh = depleted.mesh.clone();
+                *material = depleted.material.clone();
+                transform.translation = depleted.translation;
+            },
+            None => *visibility = Visibility::Hidden,
+        }
+
+        if let Some(foliage) = visuals.foliage {
+            if let Ok(mut foliage_visibility) = foliage_query.get_mut(foliage) {
+                *foliage_visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
+
+// Restore the full look of nodes that have respawned
+fn show_respawned_resources(
+    mut events: EventReader<ResourceRespawnedEvent>,
+    mut node_query: Query<(&ResourceNodeVisuals, &mut Handle<Mesh>, &mut Handle<StandardMaterial>, &mut Transform, &mut Visibility)>,
+    mut foliage_query: Query<&mut Visibility, Without<ResourceNodeVisuals>>,
+) {
+    for event in events.read() {
+        if let Ok((visuals, mut mesh, mut material, mut transform, mut visibility)) = node_query.get_mut(event.entity) {
+            *mesh = visuals.full_mesh.clone();
+            *material = visuals.full_material.clone();
+            transform.translation = visuals.full_translation;
+            *visibility = Visibility::Inherited;
+
+            if let Some(foliage) = visuals.foliage {
+                if let Ok(mut foliage_visibility) = foliage_query.get_mut(foliage) {
+                    *foliage_visibility = Visibility::Inherited;
+                }
+            }
+        }
+    }
+}
@@ -4,28 +4,49 @@ use crate::client::input::Player;
 use crate::shared::components::{Skills, Health};
 use crate::systems::skills_system::{GatheringInProgress, SkillsSettings};
 use crate::systems::combat_system::CombatState;
-use crate::client::terrain::ResourceNodeType;
-use crate::systems::inventory_system::{Inventory, ItemDatabase};
+use crate::client::terrain::{ResourceNodeType, WorldObject, nearest_world_object};
+use crate::systems::inventory_system::{Inventory, ItemDatabase, UseItemEvent};
+use crate::systems::experience::{MAX_LEVEL, experience_to_next_level, level_for_experience};
+use crate::systems::combat::combat_level;
+use crate::systems::smithing_system::{SmithingDatabase, SmeltBarEvent, SmithItemEvent, HAMMER_ITEM_ID, can_smelt, can_smith};
+use crate::systems::herblore_system::SkillBoosts;
+use crate::systems::agility_system::RunEnergy;
+use crate::systems::production_system::{ProductionDatabase, ProductionMenu, StartProductionEvent, max_batches};
+use crate::systems::prayer_system::{ActivePrayers, PrayerDatabase, PrayerPoints, TogglePrayerEvent};
+use crate::systems::slayer_system::{SlayerDatabase, SlayerProgress};
+use crate::systems::equipment_system::{Equipment, EquipmentDatabase, EquipmentSlot, UnequipItemEvent};
+use crate::systems::magic_system::{CastSpellEvent, SelectAutocastEvent, SpellDatabase, SpellEffect, Spellbook};
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(EguiPlugin)
-           .add_systems(Update, ui_system);
+           .init_resource::<InventorySelection>()
+           .add_systems(Update, (ui_system, smithing_ui_system, make_x_dialog_system, prayer_ui_system, slayer_ui_system, equipment_ui_system, magic_ui_system));
     }
 }
 
+// Item the player has selected with "Use", waiting for a second click
+#[derive(Resource, Default)]
+pub struct InventorySelection {
+    pub selected: Option<u64>,
+}
+
 fn ui_system(
     mut contexts: EguiContexts,
     player_query: Query<(&Transform, Option<&Skills>, Option<&Health>, Option<&GatheringInProgress>, Option<&CombatState>, Option<&Inventory>), With<Player>>,
-    settings: Res<SkillsSettings>,
+    mut settings: ResMut<SkillsSettings>,
     item_database: Res<ItemDatabase>,
+    mut selection: ResMut<InventorySelection>,
+    mut use_item_events: EventWriter<UseItemEvent>,
+    boosts_query: Query<&SkillBoosts, With<Player>>,
+    mut run_query: Query<&mut RunEnergy, With<Player>>,
 ) {
     let ctx = contexts.ctx_mut();
 
     // Set up the RuneScape-style UI with bottom action bar
-    setup_action_bar(ctx, &player_query, &item_database);
+    setup_action_bar(ctx, &player_query, &item_database, &mut run_query);
     // Game info window
     egui::Window::new("JamesScape")
         .resizable(false)
@@ -54,6 +75,7 @@ fn ui_system(
                 });
                 ui.vertical(|ui| {
                     ui.label("• SPACE - Jump");
+                    ui.label("• R - Toggle run");
                     ui.label("• Q/E - Rotate camera");
                     ui.label("• Mouse Wheel - Zoom");
                     ui.label("• +/- Keys - Zoom");
@@ -65,7 +87,28 @@ fn ui_system(
             ui.label(egui::RichText::new("🌲 Gathering Resources:").strong());
             ui.label("• Approach a resource (tree, rock, ore)");
             ui.label("• Press F when close to start gathering");
-            ui.label("• Wait for the progress bar to complete");
+            ui.label("• Each attempt may fail - higher levels and better tools help");
+            ui.label("• Gathering continues until your inventory is full");
+            ui.separator();
+
+            // Item controls
+            ui.label(egui::RichText::new("🎒 Using Items:").strong());
+            ui.label("• Click an item to select it, click it again to use it");
+            ui.label("• Click a different item to use the selection on it");
+            ui.label("• Right-click to cancel the selection");
+            ui.label("• Use a knife, needle or chisel on materials to make items");
+            ui.label("• Use herbs on vials of water, then add a secondary ingredient");
+            ui.label("• Use a weapon or armour to equip it");
+            ui.label("• Press F at the start of an agility obstacle to cross it");
+            ui.label("• Press T to pickpocket a nearby NPC or steal from a stall");
+            ui.label("• Use seeds, compost or plant cure at a farming patch; F to harvest");
+            ui.label("• Mine rune essence and press F at an altar to craft runes");
+            ui.label("• Use bones to bury them, or offer them at the chapel altar");
+            ui.label("• Press F at the chapel altar to recharge prayer points");
+            ui.label("• Press F next to a slayer master to get a slayer task");
+            ui.label("• Press F next to dropped loot to pick it up");
+            ui.label("• Cast teleports and alchemy from the spellbook");
+            ui.label("• If you die, press F at your gravestone to reclaim your items");
             ui.separator();
 
             // Combat controls
@@ -76,8 +119,9 @@ fn ui_system(
                     ui.label("• 2 - Switch to Ranged");
                 });
                 ui.vertical(|ui| {
-                    ui.label("• 3 - Switch to Magic");
-                    ui.label("• LMB - Attack enemy");
+                    ui.label("• 3 - Switch to Magic (casts the spellbook's autocast spell)");
+                    ui.label("• 4 - Cycle attack style");
+                    ui.label("• LMB - Attack enemy (keeps attacking until it dies or leaves range)");
                 });
             });
         });
@@ -141,6 +185,10 @@ fn ui_system(
                         ui.label(egui::RichText::new("Style:").strong());
                         ui.label(format!("{} {}", style_icon, style_name));
                     });
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Attack style:").strong());
+                        ui.label(format!("{:?}", combat_state.attack_style));
+                    });
 
                     // Target information
                     ui.horizontal(|ui| {
@@ -181,6 +229,7 @@ fn ui_system(
                     ResourceNodeType::Rock => ("🔩", "Mining", "Mining"),
                     ResourceNodeType::OreDeposit => ("💎", "Mining", "Mining"),
                     ResourceNodeType::FishingSpot => ("🎣", "Fishing", "Fishing"),
+                    ResourceNodeType::EssenceMine => ("✨", "Mining", "Mining"),
                 };
 
                 ui.heading(format!("{} {} ({})", emoji, action, skill));
@@ -200,9 +249,9 @@ fn ui_system(
 
                 ui.add(progress_bar);
 
-                // Add estimated time remaining
+                // Add time until the next success roll
                 ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Time remaining:").strong());
+                    ui.label(egui::RichText::new("Next attempt in:").strong());
                     let time_remaining = gathering.total_time - gathering.progress;
                     ui.label(format!("{:.1} seconds", time_remaining));
                 });
@@ -225,15 +274,39 @@ fn ui_system(
         .show(contexts.ctx_mut(), |ui| {
         if let Ok((_, skills_opt, _, _, _, _)) = player_query.get_single() {
             if let Some(skills) = skills_opt {
+                // Toggle for showing levels past 99
+                ui.checkbox(&mut settings.virtual_levels, "Show virtual levels");
+                ui.label(egui::RichText::new(format!("Combat level: {}", combat_level(skills))).strong());
+                ui.add_space(4.0);
+
                 // Helper function to calculate and display skill level in a formatted way
+                let settings = &*settings;
+                let boosts = boosts_query.get_single().ok();
                 let display_skill = |ui: &mut egui::Ui, name: &str, xp: u32| {
-                    let level = crate::systems::skills_system::calculate_level(xp, &settings.experience_curve);
+                    let level = settings.display_level(xp);
+                    let boost = boosts.map_or(0, |boosts| boosts.boost(&name.to_lowercase()));
+                    // Past 99 there's only a next level to reach when virtual levels are shown
+                    let next_level = match experience_to_next_level(xp).filter(|_| settings.virtual_levels || level < MAX_LEVEL) {
+                        Some(remaining) => format!("{} XP to level {}", remaining, level + 1),
+                        None => "Maximum level reached".to_string(),
+                    };
                     ui.horizontal(|ui| {
-                        ui.label(egui::RichText::new(format!("{}", name)).strong());
+                        ui.label(egui::RichText::new(format!("{}", name)).strong()).on_hover_text(next_level);
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.label(egui::RichText::new(format!("({} XP)", xp)).weak().small());
                             ui.add_space(5.0);
-                            ui.label(egui::RichText::new(format!("{}", level)).strong());
+                            // Boosted (green) or drained (red) levels show as current/real
+                            if boost != 0 {
+                                let color = if boost > 0 {
+                                    egui::Color32::from_rgb(100, 220, 100)
+                                } else {
+                                    egui::Color32::from_rgb(230, 80, 80)
+                                };
+                                let current = (level as i32 + boost).max(0);
+                                ui.label(egui::RichText::new(format!("{}/{}", current, level)).strong().color(color));
+                            } else {
+                                ui.label(egui::RichText::new(format!("{}", level)).strong());
+                            }
                         });
                     });
                 };
@@ -289,7 +362,7 @@ fn ui_system(
     });
 
     // Inventory panel - now a floating window that can be toggled
-    show_inventory_window(contexts.ctx_mut(), &player_query, &item_database);
+    show_inventory_window(contexts.ctx_mut(), &player_query, &item_database, &mut selection, &mut use_item_events);
 }
 
 // Function to set up the RuneScape-style action bar at the bottom of the screen
@@ -297,6 +370,7 @@ fn setup_action_bar(
     ctx: &mut egui::Context,
     player_query: &Query<(&Transform, Option<&Skills>, Option<&Health>, Option<&GatheringInProgress>, Option<&CombatState>, Option<&Inventory>), With<Player>>,
     _item_database: &Res<ItemDatabase>,
+    run_query: &mut Query<&mut RunEnergy, With<Player>>,
 ) {
     // Create a panel at the bottom of the screen
     egui::TopBottomPanel::bottom("action_bar")
@@ -397,6 +471,21 @@ fn setup_action_bar(
                         });
                     }
 
+                    // Run energy indicator (click to toggle running)
+                    if let Ok(mut run_energy) = run_query.get_single_mut() {
+                        let response = ui.vertical(|ui| {
+                            let icon = if run_energy.running { "🏃 Run" } else { "🚶 Walk" };
+                            ui.label(egui::RichText::new(format!("{} {}%", icon, run_energy.energy as u32)).strong());
+                            ui.add(egui::ProgressBar::new(run_energy.energy / 100.0)
+                                .desired_width(80.0)
+                                .fill(egui::Color32::from_rgb(220, 180, 60)));
+                        }).response.interact(egui::Sense::click());
+                        if response.clicked() {
+                            run_energy.running = !run_energy.running && run_energy.energy > 0.0;
+                        }
+                        response.on_hover_text("Toggle run (R)");
+                    }
+
                     // Combat style indicator
                     if let Some(combat) = combat_state {
                         ui.vertical(|ui| {
@@ -406,6 +495,7 @@ fn setup_action_bar(
                                 crate::systems::combat_system::CombatStyle::Magic => "🔮 Magic",
                             };
                             ui.label(egui::RichText::new(style_text).strong());
+                            ui.label(egui::RichText::new(format!("{:?}", combat.attack_style)).small());
                         });
                     }
 
@@ -418,6 +508,7 @@ fn setup_action_bar(
                                 crate::client::terrain::ResourceNodeType::Rock => "Mining",
                                 crate::client::terrain::ResourceNodeType::OreDeposit => "Mining",
                                 crate::client::terrain::ResourceNodeType::FishingSpot => "Fishing",
+                                crate::client::terrain::ResourceNodeType::EssenceMine => "Mining",
                             };
                             ui.label(egui::RichText::new(action).strong());
                             ui.add(egui::ProgressBar::new(progress)
@@ -447,6 +538,8 @@ fn show_inventory_window(
     ctx: &mut egui::Context,
     player_query: &Query<(&Transform, Option<&Skills>, Option<&Health>, Option<&GatheringInProgress>, Option<&CombatState>, Option<&Inventory>), With<Player>>,
     item_database: &Res<ItemDatabase>,
+    selection: &mut InventorySelection,
+    use_item_events: &mut EventWriter<UseItemEvent>,
 ) {
     egui::Window::new("Inventory")
         .resizable(false)
@@ -501,40 +594,31 @@ fn show_inventory_window(
                                     let total_slots = inventory.capacity as usize;
 
                                     if slot_index < total_slots {
-                                        // Create a framed slot with RuneScape style
+                                        // Find if there's an item for this slot
+                                        let item_for_slot = if slot_index < items.len() {
+                                            Some(items[slot_index])
+                                        } else {
+                                            None
+                                        };
+                                        let is_selected = item_for_slot
+                                            .map_or(false, |(item_id, _)| selection.selected == Some(*item_id));
+
+                                        // Create a framed slot with RuneScape style (highlighted while selected)
                                         let frame = egui::Frame::none()
-                                            .fill(egui::Color32::from_gray(50))
+                                            .fill(if is_selected { egui::Color32::from_rgb(90, 80, 30) } else { egui::Color32::from_gray(50) })
                                             .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(80)))
                                             .rounding(egui::Rounding::same(0.0))
                                             .inner_margin(egui::style::Margin::same(1.0));
 
-                                        frame.show(ui, |ui| {
+                                        let slot_response = frame.show(ui, |ui| {
                                             // Fixed size for each slot
                                             ui.set_min_size(egui::Vec2::new(slot_size, slot_size));
                                             ui.set_max_size(egui::Vec2::new(slot_size, slot_size));
 
-                                            // Find if there's an item for this slot
-                                            let item_for_slot = if slot_index < items.len() {
-                                                Some(items[slot_index])
-                                            } else {
-                                                None
-                                            };
-
                                             if let Some((item_id, quantity)) = item_for_slot {
                                                 if let Some(item_def) = item_database.items.get(item_id) {
-                                                    // Item icon (emoji based on type)
-                                                    let icon = match item_def.item_type {
-                                                        crate::systems::inventory_system::ItemType::Resource => {
-                                                            match item_def.name.as_str() {
-                                                                "Logs" => "🪵",
-                                                                "Stone" => "🪨",
-                                                                "Ore" => "💎",
-                                                                "Fish" => "🐟",
-                                                                _ => "📦",
-                                                            }
-                                                        },
-                                                        _ => "📦",
-                                                    };
+                                                    // Item icon (emoji from the item database)
+                                                    let icon = item_database.icon(*item_id);
 
                                                     // Center the icon
                                                     ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::TopDown), |ui| {
@@ -573,7 +657,25 @@ fn show_inventory_window(
                                                     });
                                                 }
                                             }
-                                        });
+                                        }).response.interact(egui::Sense::click());
+
+                                        // First click selects an item, second click uses it alone or on another item
+                                        if let Some((item_id, _)) = item_for_slot {
+                                            if slot_response.clicked() {
+                                                match selection.selected.take() {
+                                                    Some(selected) if selected == *item_id => {
+                                                        use_item_events.send(UseItemEvent { item_id: selected, target_item_id: None });
+                                                    },
+                                                    Some(selected) => {
+                                                        use_item_events.send(UseItemEvent { item_id: selected, target_item_id: Some(*item_id) });
+                                                    },
+                                                    None => selection.selected = Some(*item_id),
+                                                }
+                                            }
+                                        }
+                                        if slot_response.secondary_clicked() {
+                                            selection.selected = None;
+                                        }
                                     }
                                 }
                                 ui.end_row();
@@ -596,6 +698,14 @@ fn show_inventory_window(
                         ui.label("Items:");
                         ui.label(egui::RichText::new(format!("{} / {}", used, capacity)).color(color));
                     });
+
+                    // Show which item is waiting to be used
+                    if let Some(selected) = selection.selected {
+                        if let Some(item_def) = item_database.items.get(&selected) {
+                            ui.label(egui::RichText::new(format!("Use {} -> ...", item_def.name))
+                                .color(egui::Color32::from_rgb(255, 215, 0)));
+                        }
+                    }
                 } else {
                     ui.label("Inventory data not available");
                 }
@@ -604,3 +714,388 @@ fn show_inventory_window(
             }
         });
 }
+
+// Furnace and anvil menus, shown while the player stands next to one
+fn smithing_ui_system(
+    mut contexts: EguiContexts,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    smithing_database: Res<SmithingDatabase>,
+    item_database: Res<ItemDatabase>,
+    mut smelt_events: EventWriter<SmeltBarEvent>,
+    mut smith_events: EventWriter<SmithItemEvent>,
+) {
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+    let Some((_, object)) = nearest_world_object(
+        player_transform.translation,
+        object_query.iter(),
+        |object| matches!(object, WorldObject::Furnace | WorldObject::Anvil),
+    ) else {
+        return;
+    };
+
+    let smithing_level = level_for_experience(skills.smithing);
+    let item_name = |item_id: u64| {
+        item_database.items.get(&item_id).map_or("Unknown".to_string(), |item| item.name.clone())
+    };
+
+    egui::Window::new(object.name())
+        .resizable(false)
+        .collapsible(true)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, -120.0))
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_premultiplied(30, 30, 30, 240),
+            stroke: egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+            rounding: egui::Rounding::same(2.0),
+            inner_margin: egui::style::Margin::same(6.0),
+            outer_margin: egui::style::Margin::same(0.0),
+            ..Default::default()
+        })
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(egui::RichText::new(format!("Smithing level: {}", smithing_level)).strong());
+            ui.separator();
+
+            egui::Grid::new("smithing_grid").striped(true).show(ui, |ui| {
+                match object {
+                    WorldObject::Furnace => {
+                        for recipe in smithing_database.smelting.iter() {
+                            let ores = recipe.ores.iter()
+                                .map(|&(item_id, quantity)| format!("{} x {}", quantity, item_name(item_id)))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            let usable = smithing_level >= recipe.level_requirement && can_smelt(inventory, recipe);
+
+                            ui.label(item_name(recipe.bar_item_id));
+                            ui.label(format!("Lvl {}", recipe.level_requirement));
+                            ui.label(egui::RichText::new(ores).small());
+                            if ui.add_enabled(usable, egui::Button::new("Smelt")).clicked() {
+                                smelt_events.send(SmeltBarEvent { bar_item_id: recipe.bar_item_id });
+                            }
+                            ui.end_row();
+                        }
+                    },
+                    WorldObject::Anvil => {
+                        for recipe in smithing_database.smithing.iter() {
+                            let usable = smithing_level >= recipe.level_requirement && can_smith(inventory, recipe);
+
+                            ui.label(item_name(recipe.product_item_id));
+                            ui.label(format!("Lvl {}", recipe.level_requirement));
+                            ui.label(egui::RichText::new(format!("{} x {}", recipe.bars, item_name(recipe.bar_item_id))).small());
+                            if ui.add_enabled(usable, egui::Button::new("Smith")).clicked() {
+                                smith_events.send(SmithItemEvent { product_item_id: recipe.product_item_id });
+                            }
+                            ui.end_row();
+                        }
+                    },
+                    _ => {},
+                }
+            });
+
+            if object == WorldObject::Anvil && !inventory.items.contains_key(&HAMMER_ITEM_ID) {
+                ui.label(egui::RichText::new("You need a hammer to use the anvil.").color(egui::Color32::from_rgb(230, 80, 80)));
+            }
+        });
+}
+
+// "Make-X" dialog: pick a product and how many to make after using one item on another
+fn make_x_dialog_system(
+    mut contexts: EguiContexts,
+    player_query: Query<&Inventory, With<Player>>,
+    production_database: Res<ProductionDatabase>,
+    item_database: Res<ItemDatabase>,
+    mut menu: ResMut<ProductionMenu>,
+    mut start_events: EventWriter<StartProductionEvent>,
+) {
+    if !menu.is_open() {
+        return;
+    }
+    let Ok(inventory) = player_query.get_single() else {
+        return;
+    };
+
+    let mut open = true;
+    let options = menu.options.clone();
+
+    egui::Window::new("What would you like to make?")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -120.0))
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_premultiplied(30, 30, 30, 240),
+            stroke: egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+            rounding: egui::Rounding::same(2.0),
+            inner_margin: egui::style::Margin::same(6.0),
+            outer_margin: egui::style::Margin::same(0.0),
+            ..Default::default()
+        })
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("X =");
+                ui.add(egui::DragValue::new(&mut menu.custom_quantity).clamp_range(1..=1000));
+            });
+            ui.separator();
+
+            egui::Grid::new("make_x_grid").striped(true).show(ui, |ui| {
+                for product_item_id in options.iter() {
+                    let Some(recipe) = production_database.recipe_for_product(*product_item_id) else {
+                        continue;
+                    };
+                    let name = item_database.items.get(product_item_id)
+                        .map_or("Unknown".to_string(), |item| item.name.clone());
+                    let available = max_batches(inventory, recipe);
+
+                    ui.label(if recipe.product_quantity > 1 {
+                        format!("{} x {}", recipe.product_quantity, name)
+                    } else {
+                        name
+                    });
+                    ui.label(format!("Lvl {}", recipe.level_requirement));
+
+                    let custom_quantity = menu.custom_quantity;
+                    for (label, quantity) in [("1", 1), ("5", 5), ("10", 10), ("X", custom_quantity), ("All", available)] {
+                        if ui.add_enabled(available > 0, egui::Button::new(label)).clicked() {
+                            start_events.send(StartProductionEvent {
+                                product_item_id: *product_item_id,
+                                quantity,
+                            });
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+    if !open {
+        menu.close();
+    }
+}
+
+// Prayer book: prayer points and a toggle for each prayer
+fn prayer_ui_system(
+    mut contexts: EguiContexts,
+    player_query: Query<(&Skills, &PrayerPoints, &ActivePrayers), With<Player>>,
+    prayer_database: Res<PrayerDatabase>,
+    mut toggle_events: EventWriter<TogglePrayerEvent>,
+) {
+    let Ok((skills, points, prayers)) = player_query.get_single() else {
+        return;
+    };
+    let prayer_level = level_for_experience(skills.prayer);
+
+    egui::Window::new("Prayer")
+        .resizable(false)
+        .default_open(false)
+        .default_width(220.0)
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_premultiplied(30, 30, 30, 240),
+            stroke: egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+            rounding: egui::Rounding::same(2.0),
+            inner_margin: egui::style::Margin::same(6.0),
+            outer_margin: egui::style::Margin::same(0.0),
+            ..Default::default()
+        })
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(egui::RichText::new(format!("Prayer points: {}/{}", points.current.ceil() as u32, prayer_level)).strong());
+            ui.add(egui::ProgressBar::new(points.current / prayer_level as f32)
+                .desired_width(200.0)
+                .fill(egui::Color32::from_rgb(120, 200, 230)));
+            ui.separator();
+
+            egui::Grid::new("prayer_grid").striped(true).show(ui, |ui| {
+                for (prayer_id, prayer) in prayer_database.prayers.iter().enumerate() {
+                    let unlocked = prayer_level >= prayer.level_requirement;
+                    let active = prayers.active.contains(&prayer_id);
+
+                    let response = ui.add_enabled(unlocked, egui::SelectableLabel::new(active, prayer.name.as_str()));
+                    if response.clicked() {
+                        toggle_events.send(TogglePrayerEvent { prayer_id });
+                    }
+                    ui.label(format!("Lvl {}", prayer.level_requirement));
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+// Current slayer task and points
+fn slayer_ui_system(
+    mut contexts: EguiContexts,
+    player_query: Query<&SlayerProgress, With<Player>>,
+    slayer_database: Res<SlayerDatabase>,
+) {
+    let Ok(progress) = player_query.get_single() else {
+        return;
+    };
+
+    egui::Window::new("Slayer")
+        .resizable(false)
+        .default_open(false)
+        .default_width(220.0)
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_premultiplied(30, 30, 30, 240),
+            stroke: egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+            rounding: egui::Rounding::same(2.0),
+            inner_margin: egui::style::Margin::same(6.0),
+            outer_margin: egui::style::Margin::same(0.0),
+            ..Default::default()
+        })
+        .show(contexts.ctx_mut(), |ui| {
+            match &progress.task {
+                Some(task) => {
+                    let master = slayer_database.masters.get(task.master_id).map_or("Unknown", |master| master.name.as_str());
+                    ui.label(egui::RichText::new(format!("Task: {}", task.monster)).strong());
+                    ui.label(format!("Assigned by {}", master));
+                    ui.add(egui::ProgressBar::new((task.total - task.remaining) as f32 / task.total as f32)
+                        .desired_width(200.0)
+                        .text(format!("{} left", task.remaining)));
+                },
+                None => {
+                    ui.label("No task - visit a slayer master.");
+                },
+            }
+            ui.separator();
+            ui.label(format!("Slayer points: {}", progress.points));
+            ui.label(format!("Tasks completed: {}", progress.total_tasks_completed));
+        });
+}
+
+// Worn equipment, with a button to take each item off
+fn equipment_ui_system(
+    mut contexts: EguiContexts,
+    player_query: Query<&Equipment, With<Player>>,
+    item_database: Res<ItemDatabase>,
+    equipment_database: Res<EquipmentDatabase>,
+    mut unequip_events: EventWriter<UnequipItemEvent>,
+) {
+    let Ok(equipment) = player_query.get_single() else {
+        return;
+    };
+
+    egui::Window::new("Equipment")
+        .resizable(false)
+        .default_open(false)
+        .default_width(220.0)
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_premultiplied(30, 30, 30, 240),
+            stroke: egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+            rounding: egui::Rounding::same(2.0),
+            inner_margin: egui::style::Margin::same(6.0),
+            outer_margin: egui::style::Margin::same(0.0),
+            ..Default::default()
+        })
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Grid::new("equipment_grid").striped(true).show(ui, |ui| {
+                for slot in EquipmentSlot::ALL {
+                    ui.label(egui::RichText::new(slot.name()).strong());
+                    match equipment.slots.get(&slot) {
+                        Some(item_id) => {
+                            let name = item_database.items.get(item_id).map_or("Unknown".to_string(), |item| item.name.clone());
+                            ui.label(name);
+                            if ui.button("Remove").clicked() {
+                                unequip_events.send(UnequipItemEvent { slot });
+                            }
+                        },
+                        None => {
+                            ui.label(egui::RichText::new("Empty").weak());
+                        },
+                    }
+                    ui.end_row();
+                }
+            });
+
+            // Total equipment bonuses (melee / ranged / magic)
+            let bonuses = equipment.bonuses(&equipment_database);
+            ui.separator();
+            ui.label(format!("Attack: {:+} / {:+} / {:+}", bonuses.melee_attack, bonuses.ranged_attack, bonuses.magic_attack));
+            ui.label(format!("Strength: {:+} / {:+}", bonuses.melee_strength, bonuses.ranged_strength));
+            ui.label(format!("Defence: {:+} / {:+} / {:+}", bonuses.melee_defence, bonuses.ranged_defence, bonuses.magic_defence));
+        });
+}
+
+// Spellbook: pick a combat spell to autocast, or cast teleports and alchemy
+fn magic_ui_system(
+    mut contexts: EguiContexts,
+    player_query: Query<(&Skills, &Inventory, &Spellbook), With<Player>>,
+    spell_database: Res<SpellDatabase>,
+    item_database: Res<ItemDatabase>,
+    mut autocast_events: EventWriter<SelectAutocastEvent>,
+    mut cast_events: EventWriter<CastSpellEvent>,
+) {
+    let Ok((skills, inventory, spellbook)) = player_query.get_single() else {
+        return;
+    };
+    let magic_level = level_for_experience(skills.magic);
+    let item_name = |item_id: &u64| item_database.items.get(item_id).map_or("Unknown".to_string(), |item| item.name.clone());
+
+    egui::Window::new("Spellbook")
+        .resizable(false)
+        .default_open(false)
+        .default_width(300.0)
+        .frame(egui::Frame {
+            fill: egui::Color32::from_rgba_premultiplied(30, 30, 30, 240),
+            stroke: egui::Stroke::new(1.0, egui::Color32::from_gray(60)),
+            rounding: egui::Rounding::same(2.0),
+            inner_margin: egui::style::Margin::same(6.0),
+            outer_margin: egui::style::Margin::same(0.0),
+            ..Default::default()
+        })
+        .show(contexts.ctx_mut(), |ui| {
+            let autocast = spellbook.autocast
+                .and_then(|spell_id| spell_database.spells.get(spell_id))
+                .map_or("None".to_string(), |spell| spell.name.clone());
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(format!("Autocast: {}", autocast)).strong());
+                if spellbook.autocast.is_some() && ui.small_button("Clear").clicked() {
+                    autocast_events.send(SelectAutocastEvent { spell_id: None });
+                }
+            });
+            ui.separator();
+
+            egui::Grid::new("spellbook_grid").striped(true).show(ui, |ui| {
+                for (spell_id, spell) in spell_database.spells.iter().enumerate() {
+                    let unlocked = magic_level >= spell.level_requirement;
+                    let runes = spell.runes.iter()
+                        .map(|(item_id, amount)| format!("{} {}", amount, item_name(item_id).trim_end_matches(" rune")))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    match spell.effect {
+                        SpellEffect::Combat { .. } => {
+                            let selected = spellbook.autocast == Some(spell_id);
+                            let response = ui.add_enabled(unlocked, egui::SelectableLabel::new(selected, spell.name.as_str()));
+                            if response.clicked() {
+                                autocast_events.send(SelectAutocastEvent { spell_id: Some(spell_id) });
+                            }
+                        },
+                        SpellEffect::Teleport { .. } => {
+                            if ui.add_enabled(unlocked, egui::Button::new(spell.name.as_str())).clicked() {
+                                cast_events.send(CastSpellEvent { spell_id, target_item_id: None });
+                            }
+                        },
+                        SpellEffect::Alchemy { .. } => {
+                            // Alchemy is cast on an item chosen from the inventory
+                            ui.add_enabled_ui(unlocked, |ui| {
+                                ui.menu_button(spell.name.as_str(), |ui| {
+                                    let mut item_ids: Vec<&u64> = inventory.items.keys().collect();
+                                    item_ids.sort();
+                                    for item_id in item_ids {
+                                        if ui.button(item_name(item_id)).clicked() {
+                                            cast_events.send(CastSpellEvent { spell_id, target_item_id: Some(*item_id) });
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                            });
+                        },
+                    }
+                    ui.label(format!("Lvl {}", spell.level_requirement));
+                    let rune_color = if spell.has_runes(inventory) { egui::Color32::from_gray(200) } else { egui::Color32::from_rgb(200, 90, 90) };
+                    ui.label(egui::RichText::new(runes).small().color(rune_color));
+                    ui.end_row();
+                }
+            });
+        });
+}
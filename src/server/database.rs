@@ -1,4 +1,10 @@
 use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+use std::fs;
+use std::path::PathBuf;
+
+// Directory where persistent world state is written
+const SAVE_DIRECTORY: &str = "saves";
 
 pub struct DatabasePlugin;
 
@@ -22,3 +28,25 @@ pub fn save_player_data() {
 pub fn load_player_data() {
     // Load player data logic will go here
 }
+
+// Path of a named save file
+fn save_path(name: &str) -> PathBuf {
+    PathBuf::from(SAVE_DIRECTORY).join(format!("{}.json", name))
+}
+
+// Write some persistent state to its save file as JSON
+pub fn save_json<T: Serialize>(name: &str, data: &T) -> anyhow::Result<()> {
+    fs::create_dir_all(SAVE_DIRECTORY)?;
+    fs::write(save_path(name), serde_json::to_string_pretty(data)?)?;
+    Ok(())
+}
+
+// Read persistent state back from its save file, or None if it has never been saved
+pub fn load_json<T: DeserializeOwned>(name: &str) -> anyhow::Result<Option<T>> {
+    let path = save_path(name);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct WorldPlugin;
 
@@ -17,3 +18,12 @@ fn update_world() {
 pub fn generate_world() {
     // World generation logic will go here
 }
+
+// Seconds since the Unix epoch on the server clock. Timed world state (like crop growth)
+// is stored against this so it keeps progressing while nobody is logged in.
+pub fn server_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -41,6 +41,74 @@ pub struct Skills {
     pub runecrafting: u32,
 }
 
+// Names of every skill, in the same order as the Skills fields
+pub const SKILL_NAMES: [&str; 21] = [
+    "attack", "defense", "strength", "hitpoints", "ranged", "prayer", "magic",
+    "cooking", "woodcutting", "fletching", "fishing", "firemaking", "crafting",
+    "smithing", "mining", "herblore", "agility", "thieving", "slayer", "farming",
+    "runecrafting",
+];
+
+impl Skills {
+    // Experience in a skill by name
+    pub fn experience(&self, skill_name: &str) -> Option<u32> {
+        let experience = match skill_name {
+            "attack" => self.attack,
+            "defense" => self.defense,
+            "strength" => self.strength,
+            "hitpoints" => self.hitpoints,
+            "ranged" => self.ranged,
+            "prayer" => self.prayer,
+            "magic" => self.magic,
+            "cooking" => self.cooking,
+            "woodcutting" => self.woodcutting,
+            "fletching" => self.fletching,
+            "fishing" => self.fishing,
+            "firemaking" => self.firemaking,
+            "crafting" => self.crafting,
+            "smithing" => self.smithing,
+            "mining" => self.mining,
+            "herblore" => self.herblore,
+            "agility" => self.agility,
+            "thieving" => self.thieving,
+            "slayer" => self.slayer,
+            "farming" => self.farming,
+            "runecrafting" => self.runecrafting,
+            _ => return None,
+        };
+        Some(experience)
+    }
+
+    // Mutable experience in a skill by name
+    pub fn experience_mut(&mut self, skill_name: &str) -> Option<&mut u32> {
+        let experience = match skill_name {
+            "attack" => &mut self.attack,
+            "defense" => &mut self.defense,
+            "strength" => &mut self.strength,
+            "hitpoints" => &mut self.hitpoints,
+            "ranged" => &mut self.ranged,
+            "prayer" => &mut self.prayer,
+            "magic" => &mut self.magic,
+            "cooking" => &mut self.cooking,
+            "woodcutting" => &mut self.woodcutting,
+            "fletching" => &mut self.fletching,
+            "fishing" => &mut self.fishing,
+            "firemaking" => &mut self.firemaking,
+            "crafting" => &mut self.crafting,
+            "smithing" => &mut self.smithing,
+            "mining" => &mut self.mining,
+            "herblore" => &mut self.herblore,
+            "agility" => &mut self.agility,
+            "thieving" => &mut self.thieving,
+            "slayer" => &mut self.slayer,
+            "farming" => &mut self.farming,
+            "runecrafting" => &mut self.runecrafting,
+            _ => return None,
+        };
+        Some(experience)
+    }
+}
+
 #[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub struct Inventory {
     pub items: Vec<(u64, u32)>, // (item_id, quantity)
@@ -0,0 +1,342 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::shared::components::{Health, Skills};
+use crate::client::input::Player;
+use crate::client::physics::{MaxSpeed, Velocity};
+use crate::systems::experience::level_for_experience;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Top speeds while walking and running
+pub const WALK_SPEED: f32 = 3.0;
+pub const RUN_SPEED: f32 = 5.0;
+
+pub struct AgilityPlugin;
+
+impl Plugin for AgilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AgilityDatabase>()
+           .init_resource::<RunEnergySettings>()
+           .add_systems(Update, (
+               toggle_run,
+               update_run_energy,
+               start_obstacle,
+               process_obstacle_crossing,
+           ));
+    }
+}
+
+// Run energy settings (all rates are energy per second, out of 100)
+#[derive(Resource)]
+pub struct RunEnergySettings {
+    pub drain_rate: f32,
+    pub base_regen_rate: f32,
+    // Extra regeneration per agility level
+    pub regen_per_level: f32,
+}
+
+impl Default for RunEnergySettings {
+    fn default() -> Self {
+        Self {
+            drain_rate: 1.5,
+            base_regen_rate: 0.45,
+            regen_per_level: 0.025,
+        }
+    }
+}
+
+// Player run energy; running stops automatically when it runs out
+#[derive(Component)]
+pub struct RunEnergy {
+    pub energy: f32,
+    pub running: bool,
+}
+
+impl Default for RunEnergy {
+    fn default() -> Self {
+        Self {
+            energy: 100.0,
+            running: true,
+        }
+    }
+}
+
+// Obstacle shapes, used for the obstacle visuals
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObstacleKind {
+    LogBalance,
+    Net,
+    Wall,
+    Pipe,
+}
+
+// One obstacle of a course, crossed from start to end
+#[derive(Clone, Debug)]
+pub struct ObstacleDefinition {
+    pub name: String,
+    pub kind: ObstacleKind,
+    pub start: Vec3,
+    pub end: Vec3,
+    pub experience: u32,
+    // Chance of failing at the course's level requirement, dropping 1% per level above it
+    pub fail_chance: f32,
+    pub failure_damage: u32,
+}
+
+// A lap of obstacles that must be crossed in order
+#[derive(Clone, Debug)]
+pub struct AgilityCourse {
+    pub name: String,
+    pub level_requirement: u32,
+    pub lap_bonus_experience: u32,
+    pub obstacles: Vec<ObstacleDefinition>,
+}
+
+// Resource for agility courses (a course's ID is its index)
+#[derive(Resource)]
+pub struct AgilityDatabase {
+    pub courses: Vec<AgilityCourse>,
+    // Seconds to cross one unit of obstacle length
+    pub crossing_time_per_unit: f32,
+}
+
+impl Default for AgilityDatabase {
+    fn default() -> Self {
+        let courses = vec![
+            AgilityCourse {
+                name: "Village agility course".to_string(),
+                level_requirement: 1,
+                lap_bonus_experience: 40,
+                obstacles: vec![
+                    obstacle("Log balance", ObstacleKind::LogBalance, (-12.0, -6.0), (-12.0, 2.0), 7, 0.3, 3),
+                    obstacle("Obstacle net", ObstacleKind::Net, (-13.0, 4.0), (-17.0, 4.0), 8, 0.2, 2),
+                    obstacle("Low wall", ObstacleKind::Wall, (-19.0, 2.0), (-19.0, -1.0), 8, 0.2, 2),
+                    obstacle("Obstacle pipe", ObstacleKind::Pipe, (-18.0, -4.0), (-14.0, -8.0), 9, 0.15, 2),
+                ],
+            },
+        ];
+
+        Self {
+            courses,
+            crossing_time_per_unit: 0.5,
+        }
+    }
+}
+
+// Helper to define an obstacle on flat ground from (x, z) points
+fn obstacle(
+    name: &str,
+    kind: ObstacleKind,
+    (start_x, start_z): (f32, f32),
+    (end_x, end_z): (f32, f32),
+    experience: u32,
+    fail_chance: f32,
+    failure_damage: u32,
+) -> ObstacleDefinition {
+    ObstacleDefinition {
+        name: name.to_string(),
+        kind,
+        start: Vec3::new(start_x, 0.0, start_z),
+        end: Vec3::new(end_x, 0.0, end_z),
+        experience,
+        fail_chance,
+        failure_damage,
+    }
+}
+
+// Links an obstacle in the world to its course definition
+#[derive(Component, Debug, Clone, Copy)]
+pub struct AgilityObstacle {
+    pub course_id: usize,
+    pub index: usize,
+}
+
+// The player is crossing an obstacle and can't move until they reach the end
+#[derive(Component)]
+pub struct CrossingObstacle {
+    pub obstacle: AgilityObstacle,
+    pub from: Vec3,
+    pub to: Vec3,
+    pub progress: f32,
+    pub duration: f32,
+    pub success: bool,
+}
+
+// How far through a lap the player is
+#[derive(Component)]
+pub struct AgilityLapProgress {
+    pub course_id: usize,
+    pub next_obstacle: usize,
+}
+
+// Toggle running with the R key
+fn toggle_run(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut query: Query<&mut RunEnergy, With<Player>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::R) {
+        if let Ok(mut run_energy) = query.get_single_mut() {
+            run_energy.running = !run_energy.running && run_energy.energy > 0.0;
+        }
+    }
+}
+
+// Drain energy while running, regenerate it otherwise, and set the player's top speed
+fn update_run_energy(
+    settings: Res<RunEnergySettings>,
+    mut query: Query<(&Skills, &Velocity, &mut RunEnergy, &mut MaxSpeed), With<Player>>,
+    time: Res<Time>,
+) {
+    for (skills, velocity, mut run_energy, mut max_speed) in query.iter_mut() {
+        let moving = Vec2::new(velocity.linear.x, velocity.linear.z).length() > WALK_SPEED * 0.5;
+
+        if run_energy.running && moving {
+            run_energy.energy = (run_energy.energy - settings.drain_rate * time.delta_seconds()).max(0.0);
+            if run_energy.energy == 0.0 {
+                run_energy.running = false;
+                println!("You're out of run energy.");
+            }
+        } else {
+            let agility_level = level_for_experience(skills.agility) as f32;
+            let regen_rate = settings.base_regen_rate + agility_level * settings.regen_per_level;
+            run_energy.energy = (run_energy.energy + regen_rate * time.delta_seconds()).min(100.0);
+        }
+
+        max_speed.0 = if run_energy.running { RUN_SPEED } else { WALK_SPEED };
+    }
+}
+
+// Press F next to the start of an obstacle to attempt it
+fn start_obstacle(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    database: Res<AgilityDatabase>,
+    player_query: Query<(Entity, &Transform, &Skills), (With<Player>, Without<CrossingObstacle>)>,
+    obstacle_query: Query<&AgilityObstacle>,
+) {
+    let Some(target) = interact_events.read().last().map(|event| event.target) else {
+        return;
+    };
+    let Ok(obstacle) = obstacle_query.get(target) else {
+        return;
+    };
+    let Ok((player_entity, player_transform, skills)) = player_query.get_single() else {
+        return;
+    };
+    let Some(course) = database.courses.get(obstacle.course_id) else {
+        return;
+    };
+    let definition = &course.obstacles[obstacle.index];
+
+    let agility_level = level_for_experience(skills.agility);
+    if agility_level < course.level_requirement {
+        let message = format!("You need an agility level of {} to attempt this.", course.level_requirement);
+        println!("{}", message);
+        spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+        return;
+    }
+
+    let fail_chance = (definition.fail_chance - (agility_level - course.level_requirement) as f32 * 0.01).max(0.0);
+    let success = !rand::thread_rng().gen_bool(fail_chance as f64);
+
+    let from = Vec3::new(definition.start.x, player_transform.translation.y, definition.start.z);
+    let to = Vec3::new(definition.end.x, player_transform.translation.y, definition.end.z);
+
+    commands.entity(player_entity).insert(CrossingObstacle {
+        obstacle: *obstacle,
+        from,
+        to,
+        progress: 0.0,
+        duration: from.distance(to) * database.crossing_time_per_unit,
+        success,
+    });
+
+    println!("You attempt the {}...", definition.name.to_lowercase());
+}
+
+// Move the player across the obstacle, then award XP (and the lap bonus) or apply failure damage
+#[allow(clippy::type_complexity)]
+fn process_obstacle_crossing(
+    mut commands: Commands,
+    database: Res<AgilityDatabase>,
+    mut player_query: Query<(Entity, &mut Transform, &mut Velocity, &mut Health, &mut CrossingObstacle, Option<&mut AgilityLapProgress>), With<Player>>,
+    time: Res<Time>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+) {
+    for (entity, mut transform, mut velocity, mut health, mut crossing, lap_progress) in player_query.iter_mut() {
+        let Some(course) = database.courses.get(crossing.obstacle.course_id) else {
+            commands.entity(entity).remove::<CrossingObstacle>();
+            continue;
+        };
+        let definition = &course.obstacles[crossing.obstacle.index];
+
+        crossing.progress += time.delta_seconds();
+        // A failed attempt slips off halfway across
+        let end_fraction = if crossing.success { 1.0 } else { 0.5 };
+        let fraction = (crossing.progress / crossing.duration.max(0.01)).min(end_fraction);
+
+        velocity.linear = Vec3::ZERO;
+        let position = crossing.from.lerp(crossing.to, fraction);
+        transform.translation.x = position.x;
+        transform.translation.z = position.z;
+
+        if fraction < end_fraction {
+            continue;
+        }
+        commands.entity(entity).remove::<CrossingObstacle>();
+
+        if !crossing.success {
+            health.current = health.current.saturating_sub(definition.failure_damage);
+            transform.translation.x = crossing.from.x;
+            transform.translation.z = crossing.from.z;
+            spawn_floating_text(
+                &mut commands,
+                transform.translation + Vec3::new(0.0, 2.0, 0.0),
+                format!("You slip and fall! -{} HP", definition.failure_damage),
+                Color::rgb(0.9, 0.3, 0.3),
+            );
+            continue;
+        }
+
+        let mut experience = definition.experience;
+
+        // Track the lap; obstacles have to be done in order for the bonus
+        let index = crossing.obstacle.index;
+        let next_obstacle = match lap_progress.as_deref() {
+            Some(progress) if progress.course_id == crossing.obstacle.course_id && progress.next_obstacle == index => index + 1,
+            _ if index == 0 => 1,
+            _ => 0,
+        };
+        let next_obstacle = if next_obstacle == course.obstacles.len() {
+            experience += course.lap_bonus_experience;
+            println!("You complete a lap of the {}!", course.name);
+            0
+        } else {
+            next_obstacle
+        };
+
+        match lap_progress {
+            Some(mut progress) => {
+                progress.course_id = crossing.obstacle.course_id;
+                progress.next_obstacle = next_obstacle;
+            },
+            None => {
+                commands.entity(entity).insert(AgilityLapProgress {
+                    course_id: crossing.obstacle.course_id,
+                    next_obstacle,
+                });
+            },
+        }
+
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "agility".to_string(),
+            experience,
+        });
+        spawn_floating_text(
+            &mut commands,
+            transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("+{} agility XP", experience),
+            Color::rgb(0.9, 0.9, 0.1),
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
+use rand::Rng;
 use crate::shared::components::Skills;
+use crate::systems::experience::level_for_experience;
 
 pub struct CombatPlugin;
 
@@ -13,21 +15,133 @@ fn update_combat() {
     // Combat update logic will go here
 }
 
+// Attack, strength and defence bonuses from worn equipment
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CombatBonuses {
+    pub melee_attack: i32,
+    pub ranged_attack: i32,
+    pub magic_attack: i32,
+    pub melee_strength: i32,
+    pub ranged_strength: i32,
+    pub melee_defence: i32,
+    pub ranged_defence: i32,
+    pub magic_defence: i32,
+}
+
+impl std::ops::Add for CombatBonuses {
+    type Output = CombatBonuses;
+
+    fn add(self, other: CombatBonuses) -> CombatBonuses {
+        CombatBonuses {
+            melee_attack: self.melee_attack + other.melee_attack,
+            ranged_attack: self.ranged_attack + other.ranged_attack,
+            magic_attack: self.magic_attack + other.magic_attack,
+            melee_strength: self.melee_strength + other.melee_strength,
+            ranged_strength: self.ranged_strength + other.ranged_strength,
+            melee_defence: self.melee_defence + other.melee_defence,
+            ranged_defence: self.ranged_defence + other.ranged_defence,
+            magic_defence: self.magic_defence + other.magic_defence,
+        }
+    }
+}
+
+// One side of a fight: current combat levels (after boosts and prayers) plus equipment bonuses
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Combatant {
+    pub attack: u32,
+    pub strength: u32,
+    pub defense: u32,
+    pub ranged: u32,
+    pub magic: u32,
+    pub bonuses: CombatBonuses,
+}
+
+impl Combatant {
+    // An NPC fights at its level in every combat skill and wears nothing
+    pub fn from_level(level: u32) -> Self {
+        Self {
+            attack: level,
+            strength: level,
+            defense: level,
+            ranged: level,
+            magic: level,
+            bonuses: CombatBonuses::default(),
+        }
+    }
+
+    // Magic is defended mostly with magic, and a little with defence
+    fn magic_defence_level(&self) -> u32 {
+        (self.magic as f32 * 0.7 + self.defense as f32 * 0.3) as u32
+    }
+}
+
+// Attack or defence roll: (effective level + 8) scaled by the equipment bonus
+fn roll(level: u32, bonus: i32) -> u32 {
+    (level + 8) * (bonus + 64).max(0) as u32
+}
+
+// Chance an attack roll beats a defence roll
+pub fn hit_chance(attack_roll: u32, defence_roll: u32) -> f32 {
+    let attack_roll = attack_roll as f32;
+    let defence_roll = defence_roll as f32;
+    if attack_roll > defence_roll {
+        1.0 - (defence_roll + 2.0) / (2.0 * (attack_roll + 1.0))
+    } else {
+        attack_roll / (2.0 * (defence_roll + 1.0))
+    }
+}
+
+// Highest hit from an effective strength level and strength bonus
+fn max_hit(level: u32, strength_bonus: i32) -> u32 {
+    (0.5 + roll(level, strength_bonus) as f32 / 640.0) as u32
+}
+
+// Roll accuracy, then damage between 0 and the max hit (a miss deals 0)
+fn resolve_attack(attack_roll: u32, defence_roll: u32, max_hit: u32, rng: &mut impl Rng) -> u32 {
+    if rng.gen::<f32>() < hit_chance(attack_roll, defence_roll) {
+        rng.gen_range(0..=max_hit)
+    } else {
+        0
+    }
+}
+
 // Combat calculation functions
-#[allow(dead_code)]
-pub fn calculate_melee_damage(_attacker_skills: &Skills, _defender_skills: &Skills) -> u32 {
-    // Melee damage calculation logic will go here
-    0
+pub fn calculate_melee_damage(attacker: &Combatant, defender: &Combatant, rng: &mut impl Rng) -> u32 {
+    resolve_attack(
+        roll(attacker.attack, attacker.bonuses.melee_attack),
+        roll(defender.defense, defender.bonuses.melee_defence),
+        max_hit(attacker.strength, attacker.bonuses.melee_strength).max(1),
+        rng,
+    )
+}
+
+pub fn calculate_ranged_damage(attacker: &Combatant, defender: &Combatant, rng: &mut impl Rng) -> u32 {
+    resolve_attack(
+        roll(attacker.ranged, attacker.bonuses.ranged_attack),
+        roll(defender.defense, defender.bonuses.ranged_defence),
+        max_hit(attacker.ranged, attacker.bonuses.ranged_strength).max(1),
+        rng,
+    )
 }
 
-#[allow(dead_code)]
-pub fn calculate_ranged_damage(_attacker_skills: &Skills, _defender_skills: &Skills) -> u32 {
-    // Ranged damage calculation logic will go here
-    0
+// Magic max hits come from the spell being cast
+pub fn calculate_magic_damage(attacker: &Combatant, defender: &Combatant, max_hit: u32, rng: &mut impl Rng) -> u32 {
+    resolve_attack(
+        roll(attacker.magic, attacker.bonuses.magic_attack),
+        roll(defender.magic_defence_level(), defender.bonuses.magic_defence),
+        max_hit,
+        rng,
+    )
 }
 
-#[allow(dead_code)]
-pub fn calculate_magic_damage(_attacker_skills: &Skills, _defender_skills: &Skills) -> u32 {
-    // Magic damage calculation logic will go here
-    0
+// Combat level from the combat skills, using RuneScape's formula
+pub fn combat_level(skills: &Skills) -> u32 {
+    let level = |experience: u32| level_for_experience(experience) as f32;
+
+    let base = 0.25 * (level(skills.defense) + level(skills.hitpoints) + (level(skills.prayer) / 2.0).floor());
+    let melee = 0.325 * (level(skills.attack) + level(skills.strength));
+    let ranged = 0.325 * (level(skills.ranged) * 1.5).floor();
+    let magic = 0.325 * (level(skills.magic) * 1.5).floor();
+
+    (base + melee.max(ranged).max(magic)).floor() as u32
 }
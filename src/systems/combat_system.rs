@@ -1,7 +1,17 @@
 use bevy::prelude::*;
-use rand::Rng;
 use crate::shared::components::{Health, Skills};
+use crate::shared::entities::NPC;
 use crate::client::input::Player;
+use crate::systems::combat::{CombatBonuses, Combatant, calculate_magic_damage, calculate_melee_damage, calculate_ranged_damage};
+use crate::systems::experience::level_for_experience;
+use crate::systems::herblore_system::{SkillBoosts, boosted_level};
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent};
+use crate::systems::prayer_system::{ActivePrayers, PrayerDatabase, prayer_multiplier, protects_from};
+use crate::systems::skills_system::SkillExperienceEvent;
+use crate::systems::slayer_system::SlayerDatabase;
+use crate::systems::equipment_system::{Equipment, EquipmentDatabase};
+use crate::systems::projectile_system::{ProjectileKind, ProjectileSettings, spawn_projectile};
+use crate::systems::magic_system::{SpellDatabase, SpellEffect, Spellbook};
 
 pub struct CombatPlugin;
 
@@ -10,11 +20,11 @@ impl Plugin for CombatPlugin {
         app.init_resource::<CombatSettings>()
            .add_event::<CombatEvent>()
            .add_event::<DamageEvent>()
+           .add_event::<EnemyKilledEvent>()
            .add_systems(Update, (
                handle_combat_input,
                process_combat_events,
                process_damage_events,
-               update_enemy_ai,
            ));
     }
 }
@@ -25,8 +35,10 @@ pub struct CombatSettings {
     pub melee_range: f32,
     pub ranged_range: f32,
     pub magic_range: f32,
-    #[allow(dead_code)]
+    // Delay between attacks when unarmed; weapons scale it by their attack speed
     pub attack_cooldown: f32,
+    // Aggressive enemies leave players alone once their combat level is more than this many times the enemy's
+    pub aggression_level_multiplier: u32,
 }
 
 impl Default for CombatSettings {
@@ -36,10 +48,25 @@ impl Default for CombatSettings {
             ranged_range: 7.0,
             magic_range: 10.0,
             attack_cooldown: 1.5,
+            aggression_level_multiplier: 2,
         }
     }
 }
 
+impl CombatSettings {
+    // How far away a target can be attacked from with a combat style
+    pub fn attack_range(&self, style: CombatStyle) -> f32 {
+        match style {
+            CombatStyle::Melee => self.melee_range,
+            CombatStyle::Ranged => self.ranged_range,
+            CombatStyle::Magic => self.magic_range,
+        }
+    }
+}
+
+// Colour of spell orbs for magic attacks that aren't a spell from the spellbook
+pub const DEFAULT_SPELL_COLOR: Color = Color::rgb(0.4, 0.6, 1.0);
+
 // Combat style
 #[derive(Component, Debug, Clone, Copy, PartialEq)]
 pub enum CombatStyle {
@@ -48,10 +75,76 @@ pub enum CombatStyle {
     Magic,
 }
 
+// How a combat style is used, which decides its small level bonuses and which skills it trains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackStyle {
+    Accurate,
+    Aggressive,
+    Defensive,
+    Controlled,
+    Rapid,
+    Longrange,
+}
+
+// Hitpoints XP per point of damage, on top of the attack style's XP
+const HITPOINTS_EXPERIENCE_PER_DAMAGE: f32 = 1.33;
+
+impl AttackStyle {
+    // Attack styles available to each combat style, the first being the default
+    pub fn options(style: CombatStyle) -> &'static [AttackStyle] {
+        match style {
+            CombatStyle::Melee => &[AttackStyle::Accurate, AttackStyle::Aggressive, AttackStyle::Defensive, AttackStyle::Controlled],
+            CombatStyle::Ranged => &[AttackStyle::Accurate, AttackStyle::Rapid, AttackStyle::Longrange],
+            CombatStyle::Magic => &[AttackStyle::Accurate, AttackStyle::Defensive],
+        }
+    }
+
+    // Skills trained with this attack style and the XP each gets per point of damage
+    pub fn experience_split(&self, style: CombatStyle) -> Vec<(&'static str, f32)> {
+        match (style, self) {
+            (CombatStyle::Melee, AttackStyle::Aggressive) => vec![("strength", 4.0)],
+            (CombatStyle::Melee, AttackStyle::Defensive) => vec![("defense", 4.0)],
+            (CombatStyle::Melee, AttackStyle::Controlled) => vec![("attack", 1.33), ("strength", 1.33), ("defense", 1.33)],
+            (CombatStyle::Melee, _) => vec![("attack", 4.0)],
+            (CombatStyle::Ranged, AttackStyle::Longrange) => vec![("ranged", 2.0), ("defense", 2.0)],
+            (CombatStyle::Ranged, _) => vec![("ranged", 4.0)],
+            (CombatStyle::Magic, AttackStyle::Defensive) => vec![("magic", 1.33), ("defense", 1.0)],
+            (CombatStyle::Magic, _) => vec![("magic", 2.0)],
+        }
+    }
+
+    // Invisible level bonuses while fighting with this attack style
+    pub fn apply_bonuses(&self, style: CombatStyle, combatant: &mut Combatant) {
+        match (style, self) {
+            (CombatStyle::Melee, AttackStyle::Accurate) => combatant.attack += 3,
+            (CombatStyle::Melee, AttackStyle::Aggressive) => combatant.strength += 3,
+            (CombatStyle::Melee, AttackStyle::Controlled) => {
+                combatant.attack += 1;
+                combatant.strength += 1;
+                combatant.defense += 1;
+            },
+            (CombatStyle::Ranged, AttackStyle::Accurate) => combatant.ranged += 3,
+            (_, AttackStyle::Defensive) | (_, AttackStyle::Longrange) => combatant.defense += 3,
+            _ => {},
+        }
+    }
+
+    // Multiplier on the delay between attacks
+    pub fn speed_multiplier(&self) -> f32 {
+        if *self == AttackStyle::Rapid { 0.8 } else { 1.0 }
+    }
+
+    // Extra attack range
+    pub fn range_bonus(&self) -> f32 {
+        if *self == AttackStyle::Longrange { 2.0 } else { 0.0 }
+    }
+}
+
 // Combat state
 #[derive(Component)]
 pub struct CombatState {
     pub current_style: CombatStyle,
+    pub attack_style: AttackStyle,
     pub attack_timer: Timer,
     pub target: Option<Entity>,
 }
@@ -60,12 +153,31 @@ impl Default for CombatState {
     fn default() -> Self {
         Self {
             current_style: CombatStyle::Melee,
+            attack_style: AttackStyle::Accurate,
             attack_timer: Timer::from_seconds(1.5, TimerMode::Once),
             target: None,
         }
     }
 }
 
+impl CombatState {
+    // Switch combat style, falling back to its default attack style if the current one doesn't apply
+    pub fn set_style(&mut self, style: CombatStyle) {
+        self.current_style = style;
+        let options = AttackStyle::options(style);
+        if !options.contains(&self.attack_style) {
+            self.attack_style = options[0];
+        }
+    }
+
+    // Move on to the next attack style of the current combat style
+    pub fn cycle_attack_style(&mut self) {
+        let options = AttackStyle::options(self.current_style);
+        let index = options.iter().position(|&style| style == self.attack_style).map_or(0, |index| (index + 1) % options.len());
+        self.attack_style = options[index];
+    }
+}
+
 // Enemy component
 #[derive(Component)]
 pub struct Enemy {
@@ -80,150 +192,317 @@ pub struct CombatEvent {
     pub attacker: Entity,
     pub target: Entity,
     pub style: CombatStyle,
+    // Spellbook spell cast by a magic attack
+    pub spell: Option<usize>,
 }
 
 #[derive(Event)]
 pub struct DamageEvent {
     pub target: Entity,
     pub amount: u32,
-    #[allow(dead_code)]
     pub is_player_source: bool,
+    // Who landed the hit, if anyone did
+    pub attacker: Option<Entity>,
+}
+
+// Sent once when an enemy's health reaches zero
+#[derive(Event)]
+pub struct EnemyKilledEvent {
+    pub enemy: Entity,
+    pub by_player: bool,
 }
 
-// Handle player combat input
+// Handle player combat input: click to pick a target, then keep attacking it
+#[allow(clippy::too_many_arguments)]
 fn handle_combat_input(
     keyboard_input: Res<Input<KeyCode>>,
     mouse_button_input: Res<Input<MouseButton>>,
     settings: Res<CombatSettings>,
+    slayer_database: Res<SlayerDatabase>,
+    equipment_database: Res<EquipmentDatabase>,
+    spell_database: Res<SpellDatabase>,
     time: Res<Time>,
-    mut player_query: Query<(Entity, &Transform, &mut CombatState), With<Player>>,
-    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    mut player_query: Query<(Entity, &Transform, &mut CombatState, &Inventory, &Skills, Option<&Equipment>, Option<&Spellbook>), With<Player>>,
+    enemy_query: Query<(Entity, &Transform, &Health, Option<&NPC>), With<Enemy>>,
+    mut combat_events: EventWriter<CombatEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
 ) {
-    if let Ok((_, player_transform, mut combat_state)) = player_query.get_single_mut() {
-        // Change combat style with number keys
-        if keyboard_input.just_pressed(KeyCode::Key1) {
-            combat_state.current_style = CombatStyle::Melee;
-            println!("Switched to Melee combat style");
-        } else if keyboard_input.just_pressed(KeyCode::Key2) {
-            combat_state.current_style = CombatStyle::Ranged;
-            println!("Switched to Ranged combat style");
-        } else if keyboard_input.just_pressed(KeyCode::Key3) {
-            combat_state.current_style = CombatStyle::Magic;
-            println!("Switched to Magic combat style");
-        }
-
-        // Update attack timer
-        combat_state.attack_timer.tick(time.delta());
-
-        // Attack with left mouse button
-        if mouse_button_input.just_pressed(MouseButton::Left) && combat_state.attack_timer.finished() {
-            // Find the closest enemy within range
-            let attack_range = match combat_state.current_style {
-                CombatStyle::Melee => settings.melee_range,
-                CombatStyle::Ranged => settings.ranged_range,
-                CombatStyle::Magic => settings.magic_range,
-            };
-
-            let mut closest_enemy = None;
-            let mut closest_distance = f32::MAX;
-
-            for (enemy_entity, enemy_transform) in enemy_query.iter() {
-                let distance = player_transform.translation.distance(enemy_transform.translation);
+    let Ok((player_entity, player_transform, mut combat_state, inventory, skills, equipment, spellbook)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    // Change combat style with number keys, and attack style with 4
+    if keyboard_input.just_pressed(KeyCode::Key1) {
+        combat_state.set_style(CombatStyle::Melee);
+        println!("Switched to Melee combat style");
+    } else if keyboard_input.just_pressed(KeyCode::Key2) {
+        combat_state.set_style(CombatStyle::Ranged);
+        println!("Switched to Ranged combat style");
+    } else if keyboard_input.just_pressed(KeyCode::Key3) {
+        combat_state.set_style(CombatStyle::Magic);
+        println!("Switched to Magic combat style");
+    } else if keyboard_input.just_pressed(KeyCode::Key4) {
+        combat_state.cycle_attack_style();
+        println!("Switched to {:?} attack style", combat_state.attack_style);
+    }
 
-                if distance < attack_range && distance < closest_distance {
-                    closest_distance = distance;
-                    closest_enemy = Some(enemy_entity);
+    // Update attack timer
+    combat_state.attack_timer.tick(time.delta());
+
+    let attack_range = settings.attack_range(combat_state.current_style) + combat_state.attack_style.range_bonus();
+
+    // Select the closest living enemy in range with the left mouse button
+    if mouse_button_input.just_pressed(MouseButton::Left) {
+        let closest_enemy = enemy_query.iter()
+            .filter(|(_, _, health, _)| health.current > 0)
+            .map(|(entity, transform, _, npc)| (entity, player_transform.translation.distance(transform.translation), npc))
+            .filter(|(_, distance, _)| *distance < attack_range)
+            .min_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+
+        match closest_enemy {
+            Some((enemy_entity, _, npc)) => {
+                // Slayer monsters can only be fought with a high enough slayer level
+                let required_level = npc.map_or(1, |npc| slayer_database.level_requirement(&npc.name));
+                if level_for_experience(skills.slayer) < required_level {
+                    println!("You need a slayer level of {} to know how to wound this monster.", required_level);
+                    return;
                 }
-            }
 
-            // If an enemy is in range, attack it
-            if let Some(enemy_entity) = closest_enemy {
                 combat_state.target = Some(enemy_entity);
-                combat_state.attack_timer.reset();
+                println!("Attacking enemy with {:?} style", combat_state.current_style);
+            },
+            None => println!("No enemies in range"),
+        }
+    }
 
-                // Send combat event
-                // events.send(CombatEvent {
-                //     attacker: player_entity,
-                //     target: enemy_entity,
-                //     style: combat_state.current_style,
-                // });
+    // Keep attacking the target until it dies or leaves range
+    let Some(target) = combat_state.target else {
+        return;
+    };
+    let Ok((_, target_transform, target_health, _)) = enemy_query.get(target) else {
+        combat_state.target = None;
+        return;
+    };
+    if target_health.current == 0 {
+        combat_state.target = None;
+        return;
+    }
+    if player_transform.translation.distance(target_transform.translation) > attack_range {
+        combat_state.target = None;
+        println!("Your target is out of range.");
+        return;
+    }
+    if !combat_state.attack_timer.finished() {
+        return;
+    }
 
-                println!("Attacked enemy with {:?} style", combat_state.current_style);
-            } else {
-                println!("No enemies in range");
-            }
+    // Magic attacks cast the autocast spell, using up its runes
+    let mut spell = None;
+    if combat_state.current_style == CombatStyle::Magic {
+        let Some(spell_id) = spellbook.and_then(|spellbook| spellbook.autocast) else {
+            combat_state.target = None;
+            println!("You need to choose a spell to autocast from your spellbook.");
+            return;
+        };
+        let Some(definition) = spell_database.spells.get(spell_id) else {
+            return;
+        };
+        if level_for_experience(skills.magic) < definition.level_requirement {
+            combat_state.target = None;
+            println!("You need a magic level of {} to cast {}.", definition.level_requirement, definition.name);
+            return;
+        }
+        if !definition.has_runes(inventory) {
+            combat_state.target = None;
+            println!("You do not have enough runes to cast {}.", definition.name);
+            return;
         }
+        definition.consume_runes(&mut inventory_events);
+        spell = Some(spell_id);
     }
+
+    combat_events.send(CombatEvent {
+        attacker: player_entity,
+        target,
+        style: combat_state.current_style,
+        spell,
+    });
+
+    // The next attack waits for the weapon's attack speed
+    let cooldown = settings.attack_cooldown
+        * equipment.map_or(1.0, |equipment| equipment.attack_speed(&equipment_database))
+        * combat_state.attack_style.speed_multiplier();
+    combat_state.attack_timer = Timer::from_seconds(cooldown, TimerMode::Once);
 }
 
-// Process combat events
+// A player's combat levels (with potion boosts and prayers) and equipment bonuses
+fn player_combatant(
+    skills: &Skills,
+    boosts: Option<&SkillBoosts>,
+    prayers: Option<&ActivePrayers>,
+    prayer_database: &PrayerDatabase,
+    equipment: Option<&Equipment>,
+    equipment_database: &EquipmentDatabase,
+) -> Combatant {
+    let level = |skill_name: &str| {
+        (boosted_level(skills, boosts, skill_name) as f32 * prayer_multiplier(prayers, prayer_database, skill_name)) as u32
+    };
+
+    Combatant {
+        attack: level("attack"),
+        strength: level("strength"),
+        defense: level("defense"),
+        ranged: level("ranged"),
+        magic: level("magic"),
+        bonuses: equipment.map_or_else(CombatBonuses::default, |equipment| equipment.bonuses(equipment_database)),
+    }
+}
+
+// Process combat events: roll accuracy against the target's defence, then damage, and send it on its way
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn process_combat_events(
+    mut commands: Commands,
     mut events: EventReader<CombatEvent>,
     mut damage_events: EventWriter<DamageEvent>,
-    player_query: Query<&Skills, With<Player>>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    prayer_database: Res<PrayerDatabase>,
+    equipment_database: Res<EquipmentDatabase>,
+    player_query: Query<(&Skills, &CombatState, Option<&SkillBoosts>, Option<&ActivePrayers>, Option<&Equipment>), With<Player>>,
     enemy_query: Query<&Enemy>,
+    health_query: Query<&Health>,
+    transform_query: Query<&Transform>,
+    projectile_settings: Res<ProjectileSettings>,
+    spell_database: Res<SpellDatabase>,
 ) {
     let mut rng = rand::thread_rng();
 
     for event in events.read() {
-        // Determine if attacker is player
+        // Work out both sides of the fight; enemies fight at their level
+        let combatant = |entity: Entity| {
+            if let Ok((skills, combat_state, boosts, prayers, equipment)) = player_query.get(entity) {
+                let mut combatant = player_combatant(skills, boosts, prayers, &prayer_database, equipment, &equipment_database);
+                combat_state.attack_style.apply_bonuses(combat_state.current_style, &mut combatant);
+                Some(combatant)
+            } else {
+                enemy_query.get(entity).ok().map(|enemy| Combatant::from_level(enemy.level))
+            }
+        };
+        let (Some(attacker), Some(defender)) = (combatant(event.attacker), combatant(event.target)) else {
+            continue;
+        };
         let is_player_attacker = player_query.contains(event.attacker);
+        let Ok(attacker_position) = transform_query.get(event.attacker).map(|transform| transform.translation) else {
+            continue;
+        };
+
+        // Spells set the max hit and colour of magic attacks, and give XP just for casting them
+        let spell = event.spell
+            .and_then(|spell_id| spell_database.spells.get(spell_id))
+            .and_then(|spell| match spell.effect {
+                SpellEffect::Combat { max_hit, color } => Some(CombatSpell { max_hit, color, experience: spell.experience }),
+                _ => None,
+            });
+        if let Some(spell) = spell.filter(|_| is_player_attacker) {
+            skill_events.send(SkillExperienceEvent {
+                skill_name: "magic".to_string(),
+                experience: spell.experience,
+            });
+        }
 
-        if is_player_attacker {
-            // Player attacking enemy
-            if let Ok(skills) = player_query.get(event.attacker) {
-                // Calculate damage based on combat style and skills
-                let base_damage = match event.style {
-                    CombatStyle::Melee => {
-                        let attack_level = calculate_level(skills.attack);
-                        let strength_level = calculate_level(skills.strength);
-                        (attack_level + strength_level) / 8 + 1
-                    },
-                    CombatStyle::Ranged => {
-                        let ranged_level = calculate_level(skills.ranged);
-                        ranged_level / 4 + 1
-                    },
-                    CombatStyle::Magic => {
-                        let magic_level = calculate_level(skills.magic);
-                        magic_level / 4 + 1
-                    },
-                };
-
-                // Add some randomness
-                let damage = (base_damage as f32 * rng.gen_range(0.8..1.2)) as u32;
-
-                // Send damage event
-                damage_events.send(DamageEvent {
-                    target: event.target,
-                    amount: damage,
-                    is_player_source: true,
-                });
-            }
-        } else {
-            // Enemy attacking player
-            if let Ok(enemy) = enemy_query.get(event.attacker) {
-                // Calculate damage based on enemy level
-                let base_damage = enemy.level / 4 + 1;
-
-                // Add some randomness
-                let damage = (base_damage as f32 * rng.gen_range(0.8..1.2)) as u32;
-
-                // Send damage event
-                damage_events.send(DamageEvent {
-                    target: event.target,
-                    amount: damage,
-                    is_player_source: false,
-                });
+        let mut damage = match event.style {
+            CombatStyle::Melee => calculate_melee_damage(&attacker, &defender, &mut rng),
+            CombatStyle::Ranged => calculate_ranged_damage(&attacker, &defender, &mut rng),
+            CombatStyle::Magic => calculate_magic_damage(&attacker, &defender, spell.map_or(attacker.magic / 4 + 1, |spell| spell.max_hit), &mut rng),
+        };
+
+        // Protection prayers block enemy attacks of their style completely
+        if !is_player_attacker {
+            let prayers = player_query.get(event.target).ok().and_then(|(_, _, _, prayers, _)| prayers);
+            if protects_from(prayers, &prayer_database, event.style) {
+                damage = 0;
             }
         }
+
+        let hit = PendingHit {
+            attacker: event.attacker,
+            target: event.target,
+            style: event.style,
+            damage,
+            is_player_source: is_player_attacker,
+            // Players train combat skills in proportion to the damage they deal
+            experience: player_query.get(event.attacker)
+                .map(|(_, combat_state, _, _, _)| combat_state.attack_style.experience_split(event.style))
+                .unwrap_or_default(),
+        };
+
+        // Melee lands straight away; arrows and spells have to fly to the target first
+        match event.style {
+            CombatStyle::Melee => deal_hit(&hit, health_query.get(event.target).ok(), &mut damage_events, &mut skill_events),
+            CombatStyle::Ranged => spawn_projectile(&mut commands, &projectile_settings, attacker_position, hit, ProjectileKind::Arrow),
+            CombatStyle::Magic => {
+                let color = spell.map_or(DEFAULT_SPELL_COLOR, |spell| spell.color);
+                spawn_projectile(&mut commands, &projectile_settings, attacker_position, hit, ProjectileKind::SpellOrb { color });
+            },
+        }
     }
 }
 
+// The parts of a combat spell an attack needs
+#[derive(Clone, Copy)]
+struct CombatSpell {
+    max_hit: u32,
+    color: Color,
+    experience: u32,
+}
+
+// A rolled attack waiting to be dealt, straight away for melee or when a projectile lands
+#[derive(Clone, Debug)]
+pub struct PendingHit {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub style: CombatStyle,
+    // Rolled damage (0 is a miss)
+    pub damage: u32,
+    pub is_player_source: bool,
+    // Skills the attacker trains and the XP each gets per point of damage (hitpoints is added on top)
+    pub experience: Vec<(&'static str, f32)>,
+}
+
+// Deal a hit: cap it at the target's remaining health, send the damage and award combat XP
+pub fn deal_hit(
+    hit: &PendingHit,
+    target_health: Option<&Health>,
+    damage_events: &mut EventWriter<DamageEvent>,
+    skill_events: &mut EventWriter<SkillExperienceEvent>,
+) {
+    // A hit can't do more damage than the target has health left
+    let damage = target_health.map_or(hit.damage, |health| hit.damage.min(health.current));
+
+    if damage > 0 && !hit.experience.is_empty() {
+        let hitpoints = ("hitpoints", HITPOINTS_EXPERIENCE_PER_DAMAGE);
+        for &(skill_name, experience_per_damage) in hit.experience.iter().chain([&hitpoints]) {
+            skill_events.send(SkillExperienceEvent {
+                skill_name: skill_name.to_string(),
+                experience: (damage as f32 * experience_per_damage).round() as u32,
+            });
+        }
+    }
+
+    // Send damage event (0 is a miss)
+    damage_events.send(DamageEvent {
+        target: hit.target,
+        amount: damage,
+        is_player_source: hit.is_player_source,
+        attacker: Some(hit.attacker),
+    });
+}
+
 // Process damage events
 fn process_damage_events(
     mut events: EventReader<DamageEvent>,
     mut player_query: Query<&mut Health, With<Player>>,
     mut enemy_query: Query<&mut Health, Without<Player>>,
+    mut killed_events: EventWriter<EnemyKilledEvent>,
 ) {
     for event in events.read() {
         if player_query.contains(event.target) {
@@ -235,54 +514,17 @@ fn process_damage_events(
         } else {
             // Enemy taking damage
             if let Ok(mut health) = enemy_query.get_mut(event.target) {
+                let was_alive = health.current > 0;
                 health.current = health.current.saturating_sub(event.amount);
                 println!("Enemy took {} damage. Health: {}/{}", event.amount, health.current, health.maximum);
-            }
-        }
-    }
-}
 
-// Update enemy AI
-fn update_enemy_ai(
-    time: Res<Time>,
-    mut enemy_query: Query<(Entity, &Transform, &Enemy, &mut CombatState)>,
-    player_query: Query<(Entity, &Transform), With<Player>>,
-    mut combat_events: EventWriter<CombatEvent>,
-) {
-    if let Ok((player_entity, player_transform)) = player_query.get_single() {
-        for (enemy_entity, enemy_transform, enemy, mut combat_state) in enemy_query.iter_mut() {
-            // Update attack timer
-            combat_state.attack_timer.tick(time.delta());
-
-            // Calculate distance to player
-            let distance = enemy_transform.translation.distance(player_transform.translation);
-
-            // Check if player is in aggression range
-            if distance < enemy.aggression_range {
-                // Set player as target
-                combat_state.target = Some(player_entity);
-
-                // Attack if in range and cooldown is finished
-                if distance < enemy.attack_range && combat_state.attack_timer.finished() {
-                    combat_state.attack_timer.reset();
-
-                    // Send combat event
-                    combat_events.send(CombatEvent {
-                        attacker: enemy_entity,
-                        target: player_entity,
-                        style: CombatStyle::Melee, // Enemies use melee by default
+                if was_alive && health.current == 0 {
+                    killed_events.send(EnemyKilledEvent {
+                        enemy: event.target,
+                        by_player: event.is_player_source,
                     });
                 }
-            } else {
-                // Clear target if player is out of range
-                combat_state.target = None;
             }
         }
     }
 }
-
-// Helper function to calculate level from experience
-fn calculate_level(experience: u32) -> u32 {
-    // Simple level calculation for now
-    (experience as f32 / 100.0).sqrt() as u32 + 1
-}
@@ -0,0 +1,412 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::shared::components::{Health, Skills};
+use crate::client::input::Player;
+use crate::client::terrain::{WorldObject, WORLD_OBJECT_RANGE, nearest_world_object};
+use crate::systems::experience::level_for_experience;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, UseItemEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Item ID of the tinderbox used to light fires
+pub const TINDERBOX_ITEM_ID: u64 = 31;
+
+pub struct CookingPlugin;
+
+impl Plugin for CookingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CookingDatabase>()
+           .add_systems(Update, (
+               handle_firemaking,
+               start_cooking,
+               process_cooking,
+               burn_out_fires,
+               eat_food,
+           ));
+    }
+}
+
+// A fire lit on the ground, which burns out after a while
+#[derive(Component)]
+pub struct Fire {
+    pub lifetime: Timer,
+}
+
+// Cooking state (progress counts towards the next item being cooked)
+#[derive(Component)]
+pub struct CookingInProgress {
+    pub raw_item_id: u64,
+    pub source: Entity,
+    pub remaining: u32,
+    pub progress: f32,
+    pub total_time: f32,
+}
+
+// Logs that can be lit into a fire
+#[derive(Clone, Debug)]
+pub struct FiremakingDefinition {
+    #[allow(dead_code)]
+    pub logs_item_id: u64,
+    pub level_requirement: u32,
+    pub experience: u32,
+    // Seconds the fire burns for
+    pub burn_time: f32,
+}
+
+// Raw food that can be cooked on a fire or range
+#[derive(Clone, Debug)]
+pub struct CookingRecipe {
+    pub raw_item_id: u64,
+    pub cooked_item_id: u64,
+    pub burnt_item_id: u64,
+    pub level_requirement: u32,
+    pub experience: u32,
+    // Level at which the food stops burning on a fire (ranges are a little more forgiving)
+    pub stop_burn_level: u32,
+    // Health restored when the cooked food is eaten
+    pub heals: u32,
+}
+
+// Resource for cooking and firemaking data
+#[derive(Resource)]
+pub struct CookingDatabase {
+    pub logs: HashMap<u64, FiremakingDefinition>,
+    pub recipes: HashMap<u64, CookingRecipe>,
+    pub cooking_time: f32,
+}
+
+impl Default for CookingDatabase {
+    fn default() -> Self {
+        let mut logs = HashMap::new();
+        insert_logs(&mut logs, 1, 1, 40, 60.0);    // Logs
+        insert_logs(&mut logs, 10, 15, 60, 75.0);  // Oak logs
+        insert_logs(&mut logs, 11, 30, 90, 90.0);  // Willow logs
+        insert_logs(&mut logs, 12, 60, 202, 120.0); // Yew logs
+
+        let mut recipes = HashMap::new();
+        insert_recipe(&mut recipes, 4, 40, 41, 1, 30, 34, 10);   // Shrimps
+        insert_recipe(&mut recipes, 17, 42, 43, 15, 70, 50, 20); // Trout
+        insert_recipe(&mut recipes, 18, 44, 45, 40, 120, 74, 30); // Lobster
+
+        Self {
+            logs,
+            recipes,
+            cooking_time: 1.8,
+        }
+    }
+}
+
+impl CookingDatabase {
+    // Find the recipe that produces a cooked item
+    pub fn recipe_for_cooked(&self, cooked_item_id: u64) -> Option<&CookingRecipe> {
+        self.recipes.values().find(|recipe| recipe.cooked_item_id == cooked_item_id)
+    }
+}
+
+// Chance of burning food at a cooking level
+pub fn burn_chance(cooking_level: u32, recipe: &CookingRecipe, on_range: bool) -> f32 {
+    let stop_burn_level = if on_range {
+        recipe.stop_burn_level.saturating_sub(3).max(recipe.level_requirement)
+    } else {
+        recipe.stop_burn_level
+    };
+
+    if cooking_level >= stop_burn_level {
+        return 0.0;
+    }
+
+    // Burn chance falls off linearly from 60% at the level requirement to 0% at the stop-burn level
+    let span = (stop_burn_level - recipe.level_requirement).max(1) as f32;
+    let levels_left = (stop_burn_level - cooking_level) as f32;
+    (levels_left / span * 0.6).clamp(0.0, 0.6)
+}
+
+// Use a tinderbox on logs (or logs on a tinderbox) to light a fire
+fn handle_firemaking(
+    mut commands: Commands,
+    mut events: EventReader<UseItemEvent>,
+    database: Res<CookingDatabase>,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+
+    for event in events.read() {
+        let logs_item_id = match (event.item_id, event.target_item_id) {
+            (TINDERBOX_ITEM_ID, Some(target)) => target,
+            (item_id, Some(TINDERBOX_ITEM_ID)) => item_id,
+            _ => continue,
+        };
+        let Some(definition) = database.logs.get(&logs_item_id) else {
+            continue;
+        };
+        if !inventory.items.contains_key(&logs_item_id) {
+            continue;
+        }
+
+        let firemaking_level = level_for_experience(skills.firemaking);
+        if firemaking_level < definition.level_requirement {
+            let message = format!("You need a firemaking level of {} to burn these logs.", definition.level_requirement);
+            println!("{}", message);
+            spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+            continue;
+        }
+
+        // Light the fire on the ground just in front of the player
+        let mut fire_position = player_transform.translation + player_transform.forward() * 1.0;
+        fire_position.y = 0.0;
+
+        let fire_nearby = object_query.iter()
+            .any(|(_, transform, object)| *object == WorldObject::Fire && transform.translation.distance(fire_position) < 1.0);
+        if fire_nearby {
+            println!("You can't light a fire here.");
+            continue;
+        }
+
+        // Higher levels light fires more reliably
+        let light_chance = (0.5 + (firemaking_level - definition.level_requirement) as f32 * 0.02).min(1.0);
+        if !rng.gen_bool(light_chance as f64) {
+            println!("You fail to light the fire.");
+            continue;
+        }
+
+        commands.spawn((
+            SpatialBundle {
+                transform: Transform::from_translation(fire_position),
+                ..default()
+            },
+            WorldObject::Fire,
+            Fire {
+                lifetime: Timer::from_seconds(definition.burn_time, TimerMode::Once),
+            },
+        ));
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: logs_item_id,
+            quantity: -1,
+        });
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "firemaking".to_string(),
+            experience: definition.experience,
+        });
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("+{} firemaking XP", definition.experience),
+            Color::rgb(0.9, 0.9, 0.1),
+        );
+
+        println!("The fire catches and the logs begin to burn.");
+    }
+}
+
+// Use raw food near a fire or range to start cooking it
+fn start_cooking(
+    mut commands: Commands,
+    mut events: EventReader<UseItemEvent>,
+    database: Res<CookingDatabase>,
+    player_query: Query<(Entity, &Transform, &Skills, &Inventory), (With<Player>, Without<CookingInProgress>)>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+) {
+    let Ok((player_entity, player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.target_item_id.is_some() {
+            continue;
+        }
+        let Some(recipe) = database.recipes.get(&event.item_id) else {
+            continue;
+        };
+
+        let Some((source, object)) = nearest_world_object(
+            player_transform.translation,
+            object_query.iter(),
+            |object| matches!(object, WorldObject::Fire | WorldObject::Range),
+        ) else {
+            println!("You need to be next to a fire or range to cook this.");
+            continue;
+        };
+
+        if level_for_experience(skills.cooking) < recipe.level_requirement {
+            let message = format!("You need a cooking level of {} to cook this.", recipe.level_requirement);
+            println!("{}", message);
+            spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+            continue;
+        }
+
+        let remaining = inventory.items.get(&recipe.raw_item_id).copied().unwrap_or(0);
+        if remaining == 0 {
+            continue;
+        }
+
+        commands.entity(player_entity).insert(CookingInProgress {
+            raw_item_id: recipe.raw_item_id,
+            source,
+            remaining,
+            progress: 0.0,
+            total_time: database.cooking_time,
+        });
+
+        println!("You start cooking on the {}.", object.name().to_lowercase());
+        break;
+    }
+}
+
+// Cook one item per cooking tick until the food runs out or the fire goes out
+fn process_cooking(
+    mut commands: Commands,
+    database: Res<CookingDatabase>,
+    mut player_query: Query<(Entity, &Transform, &Skills, &Inventory, &mut CookingInProgress), With<Player>>,
+    object_query: Query<(&Transform, &WorldObject)>,
+    time: Res<Time>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for (entity, player_transform, skills, inventory, mut cooking) in player_query.iter_mut() {
+        let Some(recipe) = database.recipes.get(&cooking.raw_item_id) else {
+            commands.entity(entity).remove::<CookingInProgress>();
+            continue;
+        };
+
+        // Stop if the fire went out or the player walked away
+        let Ok((source_transform, source_object)) = object_query.get(cooking.source) else {
+            println!("The fire has gone out.");
+            commands.entity(entity).remove::<CookingInProgress>();
+            continue;
+        };
+        if source_transform.translation.distance(player_transform.translation) > WORLD_OBJECT_RANGE
+            || !inventory.items.contains_key(&cooking.raw_item_id)
+        {
+            commands.entity(entity).remove::<CookingInProgress>();
+            continue;
+        }
+
+        cooking.progress += time.delta_seconds();
+        if cooking.progress < cooking.total_time {
+            continue;
+        }
+        cooking.progress -= cooking.total_time;
+
+        let on_range = *source_object == WorldObject::Range;
+        let burnt = rng.gen_bool(burn_chance(level_for_experience(skills.cooking), recipe, on_range) as f64);
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: recipe.raw_item_id,
+            quantity: -1,
+        });
+
+        if burnt {
+            inventory_events.send(InventoryUpdateEvent {
+                item_id: recipe.burnt_item_id,
+                quantity: 1,
+            });
+            println!("You accidentally burn the food.");
+        } else {
+            inventory_events.send(InventoryUpdateEvent {
+                item_id: recipe.cooked_item_id,
+                quantity: 1,
+            });
+            skill_events.send(SkillExperienceEvent {
+                skill_name: "cooking".to_string(),
+                experience: recipe.experience,
+            });
+            spawn_floating_text(
+                &mut commands,
+                player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+                format!("+{} cooking XP", recipe.experience),
+                Color::rgb(0.9, 0.9, 0.1),
+            );
+        }
+
+        cooking.remaining = cooking.remaining.saturating_sub(1);
+        if cooking.remaining == 0 {
+            commands.entity(entity).remove::<CookingInProgress>();
+        }
+    }
+}
+
+// Remove fires once they have burned out
+fn burn_out_fires(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Fire)>,
+    time: Res<Time>,
+) {
+    for (entity, mut fire) in query.iter_mut() {
+        fire.lifetime.tick(time.delta());
+
+        if fire.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// Use cooked food to eat it and restore health
+fn eat_food(
+    mut events: EventReader<UseItemEvent>,
+    database: Res<CookingDatabase>,
+    mut player_query: Query<(&Inventory, &mut Health), With<Player>>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((inventory, mut health)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.target_item_id.is_some() || !inventory.items.contains_key(&event.item_id) {
+            continue;
+        }
+        let Some(recipe) = database.recipe_for_cooked(event.item_id) else {
+            continue;
+        };
+
+        health.current = (health.current + recipe.heals).min(health.maximum);
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: event.item_id,
+            quantity: -1,
+        });
+
+        println!("You eat the food. It heals some health. Health: {}/{}", health.current, health.maximum);
+    }
+}
+
+// Helper to add burnable logs to the database
+fn insert_logs(logs: &mut HashMap<u64, FiremakingDefinition>, logs_item_id: u64, level_requirement: u32, experience: u32, burn_time: f32) {
+    logs.insert(logs_item_id, FiremakingDefinition {
+        logs_item_id,
+        level_requirement,
+        experience,
+        burn_time,
+    });
+}
+
+// Helper to add a cooking recipe to the database
+#[allow(clippy::too_many_arguments)]
+fn insert_recipe(
+    recipes: &mut HashMap<u64, CookingRecipe>,
+    raw_item_id: u64,
+    cooked_item_id: u64,
+    burnt_item_id: u64,
+    level_requirement: u32,
+    experience: u32,
+    stop_burn_level: u32,
+    heals: u32,
+) {
+    recipes.insert(raw_item_id, CookingRecipe {
+        raw_item_id,
+        cooked_item_id,
+        burnt_item_id,
+        level_requirement,
+        experience,
+        stop_burn_level,
+        heals,
+    });
+}
@@ -0,0 +1,301 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::f32::consts::FRAC_PI_2;
+use crate::shared::components::{Health, Skills};
+use crate::client::input::Player;
+use crate::client::physics::Velocity;
+use crate::client::terrain::WorldObject;
+use crate::systems::agility_system::RunEnergy;
+use crate::systems::combat_system::{CombatState, Enemy};
+use crate::systems::equipment_system::Equipment;
+use crate::systems::experience::level_for_experience;
+use crate::systems::herblore_system::SkillBoosts;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, ItemDatabase};
+use crate::systems::prayer_system::{ActivePrayers, PrayerPoints};
+use crate::systems::skills_system::spawn_floating_text;
+
+pub struct DeathPlugin;
+
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DeathSettings>()
+           .add_systems(Update, (
+               start_enemy_deaths,
+               animate_enemy_deaths,
+               handle_player_death,
+               reclaim_gravestone,
+               expire_gravestones,
+           ));
+    }
+}
+
+// Death settings
+#[derive(Resource)]
+pub struct DeathSettings {
+    // Where players respawn after dying
+    pub home_position: Vec3,
+    // How many of their most valuable items players keep
+    pub items_kept: usize,
+    // Seconds a gravestone lasts before its items are lost
+    pub gravestone_duration: f32,
+    // Seconds an enemy's death animation lasts before it's removed
+    pub death_animation_duration: f32,
+}
+
+impl Default for DeathSettings {
+    fn default() -> Self {
+        Self {
+            home_position: Vec3::new(0.0, 1.0, 0.0),
+            items_kept: 3,
+            gravestone_duration: 300.0,
+            death_animation_duration: 1.5,
+        }
+    }
+}
+
+// An enemy toppling over and sinking into the ground before it's removed
+#[derive(Component)]
+pub struct DeathAnimation {
+    pub timer: Timer,
+    pub start_transform: Transform,
+}
+
+// Items a player left behind when they died, reclaimable until the timer runs out
+#[derive(Component)]
+pub struct Gravestone {
+    pub items: HashMap<u64, u32>,
+    pub gold: u32,
+    pub timer: Timer,
+}
+
+// Enemies at zero health stop fighting and start their death animation
+fn start_enemy_deaths(
+    mut commands: Commands,
+    settings: Res<DeathSettings>,
+    mut enemy_query: Query<(Entity, &Transform, &Health, &mut CombatState), (With<Enemy>, Without<DeathAnimation>)>,
+) {
+    for (entity, transform, health, mut combat_state) in enemy_query.iter_mut() {
+        if health.current > 0 {
+            continue;
+        }
+
+        combat_state.target = None;
+        commands.entity(entity).insert(DeathAnimation {
+            timer: Timer::from_seconds(settings.death_animation_duration, TimerMode::Once),
+            start_transform: *transform,
+        });
+    }
+}
+
+// Topple dying enemies onto their side, sink them into the ground, then remove them
+fn animate_enemy_deaths(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut DeathAnimation)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut animation) in query.iter_mut() {
+        animation.timer.tick(time.delta());
+        let progress = animation.timer.percent();
+
+        // Fall over during the first half, then sink during the second
+        let fall = (progress * 2.0).min(1.0);
+        let sink = ((progress - 0.5) * 2.0).max(0.0);
+        transform.rotation = animation.start_transform.rotation * Quat::from_rotation_z(fall * FRAC_PI_2);
+        transform.translation = animation.start_transform.translation - Vec3::Y * sink;
+
+        if animation.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// Split a player's items into the most valuable few they keep and the rest they drop.
+// Each item of a stack counts separately, so a stack can be partly kept.
+fn items_kept_on_death(
+    inventory: &Inventory,
+    equipment: &Equipment,
+    item_database: &ItemDatabase,
+    items_kept: usize,
+) -> Vec<u64> {
+    let value = |item_id: u64| item_database.items.get(&item_id).map_or(0, |item| item.value);
+
+    let mut candidates: Vec<u64> = inventory.items.iter()
+        .flat_map(|(&item_id, &quantity)| std::iter::repeat(item_id).take((quantity as usize).min(items_kept)))
+        .chain(equipment.slots.values().copied())
+        .collect();
+    candidates.sort_by_key(|&item_id| std::cmp::Reverse(value(item_id)));
+    candidates.truncate(items_kept);
+    candidates
+}
+
+// When the player dies, keep their most valuable items, leave the rest in a gravestone and send them home
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn handle_player_death(
+    mut commands: Commands,
+    settings: Res<DeathSettings>,
+    item_database: Res<ItemDatabase>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut player_query: Query<(
+        &mut Transform,
+        &mut Health,
+        &mut Velocity,
+        &mut CombatState,
+        &mut Inventory,
+        &mut Equipment,
+        &Skills,
+        (Option<&mut PrayerPoints>, Option<&mut ActivePrayers>, Option<&mut SkillBoosts>, Option<&mut RunEnergy>),
+    ), With<Player>>,
+    mut enemy_query: Query<&mut CombatState, (With<Enemy>, Without<Player>)>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((mut transform, mut health, mut velocity, mut combat_state, mut inventory, mut equipment, skills, restorable)) = player_query.get_single_mut() else {
+        return;
+    };
+    if health.current > 0 {
+        return;
+    }
+    let death_position = transform.translation;
+
+    // Kept items that are worn stay worn; anything else worn is dropped
+    let mut kept = items_kept_on_death(&inventory, &equipment, &item_database, settings.items_kept);
+    let mut dropped: HashMap<u64, u32> = HashMap::new();
+    equipment.slots.retain(|_, item_id| {
+        if let Some(index) = kept.iter().position(|kept_id| *kept_id == *item_id) {
+            kept.remove(index);
+            true
+        } else {
+            *dropped.entry(*item_id).or_insert(0) += 1;
+            false
+        }
+    });
+
+    // The remaining kept items stay in the inventory; the rest of it is dropped
+    let mut kept_in_inventory: HashMap<u64, u32> = HashMap::new();
+    for item_id in kept {
+        *kept_in_inventory.entry(item_id).or_insert(0) += 1;
+    }
+    for (&item_id, &quantity) in inventory.items.iter() {
+        let lost = quantity - kept_in_inventory.get(&item_id).map_or(0, |&kept| kept.min(quantity));
+        if lost > 0 {
+            *dropped.entry(item_id).or_insert(0) += lost;
+            inventory_events.send(InventoryUpdateEvent {
+                item_id,
+                quantity: -(lost as i32),
+            });
+        }
+    }
+    let gold = std::mem::take(&mut inventory.gold);
+
+    // Everything else waits in a gravestone where the player fell
+    if !dropped.is_empty() || gold > 0 {
+        commands.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(0.6, 0.9, 0.2))),
+                material: materials.add(Color::rgb(0.55, 0.55, 0.55).into()),
+                transform: Transform::from_xyz(death_position.x, 0.45, death_position.z),
+                ..default()
+            },
+            WorldObject::Gravestone,
+            Gravestone {
+                items: dropped,
+                gold,
+                timer: Timer::from_seconds(settings.gravestone_duration, TimerMode::Once),
+            },
+        ));
+    }
+
+    // Respawn at home with full health and fresh stats
+    health.current = health.maximum;
+    transform.translation = settings.home_position;
+    velocity.linear = Vec3::ZERO;
+    combat_state.target = None;
+
+    let (prayer_points, active_prayers, boosts, run_energy) = restorable;
+    if let Some(mut prayer_points) = prayer_points {
+        prayer_points.current = level_for_experience(skills.prayer) as f32;
+    }
+    if let Some(mut active_prayers) = active_prayers {
+        active_prayers.active.clear();
+    }
+    if let Some(mut boosts) = boosts {
+        boosts.levels.clear();
+    }
+    if let Some(mut run_energy) = run_energy {
+        run_energy.energy = 100.0;
+    }
+
+    // Enemies lose interest in a dead player
+    for mut enemy_state in enemy_query.iter_mut() {
+        enemy_state.target = None;
+    }
+
+    let minutes = settings.gravestone_duration / 60.0;
+    let message = format!("Oh dear, you are dead! Your items will wait at your gravestone for {} minutes.", minutes as u32);
+    println!("{}", message);
+    spawn_floating_text(&mut commands, settings.home_position + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+}
+
+// Press F at a gravestone to take back as many of its items as there's room for
+fn reclaim_gravestone(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    mut player_query: Query<(&Transform, &mut Inventory), With<Player>>,
+    mut gravestone_query: Query<&mut Gravestone>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Some(gravestone_entity) = interact_events.read().last().map(|event| event.target) else {
+        return;
+    };
+    let Ok(mut gravestone) = gravestone_query.get_mut(gravestone_entity) else {
+        return;
+    };
+    let Ok((player_transform, mut inventory)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    inventory.gold += std::mem::take(&mut gravestone.gold);
+
+    // Updates land after this system, so count the slots taken by items reclaimed so far
+    let mut free_slots = inventory.capacity.saturating_sub(inventory.items.len() as u32);
+    let items: Vec<(u64, u32)> = gravestone.items.iter().map(|(&item_id, &quantity)| (item_id, quantity)).collect();
+    for (item_id, quantity) in items {
+        if !inventory.items.contains_key(&item_id) {
+            if free_slots == 0 {
+                continue;
+            }
+            free_slots -= 1;
+        }
+        inventory_events.send(InventoryUpdateEvent {
+            item_id,
+            quantity: quantity as i32,
+        });
+        gravestone.items.remove(&item_id);
+    }
+
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+    if gravestone.items.is_empty() {
+        commands.entity(gravestone_entity).despawn_recursive();
+        spawn_floating_text(&mut commands, text_position, "You retrieve your items.".to_string(), Color::rgb(0.4, 0.9, 0.6));
+    } else {
+        let message = format!("You don't have room for everything; {} seconds left to collect the rest.", gravestone.timer.remaining_secs() as u32);
+        println!("{}", message);
+        spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.9, 0.3));
+    }
+}
+
+// Gravestones crumble when their timer runs out, and their items are lost
+fn expire_gravestones(
+    mut commands: Commands,
+    mut gravestone_query: Query<(Entity, &mut Gravestone)>,
+    time: Res<Time>,
+) {
+    for (entity, mut gravestone) in gravestone_query.iter_mut() {
+        gravestone.timer.tick(time.delta());
+        if gravestone.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            println!("Your gravestone has crumbled, and the items in it are lost.");
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::client::input::Player;
+use crate::systems::combat::CombatBonuses;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, UseItemEvent};
+
+pub struct EquipmentPlugin;
+
+impl Plugin for EquipmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EquipmentDatabase>()
+           .add_event::<UnequipItemEvent>()
+           .add_systems(Update, (
+               equip_items,
+               unequip_items,
+           ));
+    }
+}
+
+// Where an item is worn
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EquipmentSlot {
+    Weapon,
+    Body,
+    Hands,
+    Feet,
+}
+
+impl EquipmentSlot {
+    pub const ALL: [EquipmentSlot; 4] = [EquipmentSlot::Weapon, EquipmentSlot::Body, EquipmentSlot::Hands, EquipmentSlot::Feet];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EquipmentSlot::Weapon => "Weapon",
+            EquipmentSlot::Body => "Body",
+            EquipmentSlot::Hands => "Hands",
+            EquipmentSlot::Feet => "Feet",
+        }
+    }
+}
+
+// Stats of an item that can be worn
+#[derive(Clone, Debug)]
+pub struct EquipmentStats {
+    pub slot: EquipmentSlot,
+    // Delay between attacks as a multiple of CombatSettings::attack_cooldown (weapons only)
+    pub attack_speed: f32,
+    pub bonuses: CombatBonuses,
+}
+
+// Resource for wearable items, keyed by item ID
+#[derive(Resource)]
+pub struct EquipmentDatabase {
+    pub items: HashMap<u64, EquipmentStats>,
+}
+
+impl Default for EquipmentDatabase {
+    fn default() -> Self {
+        let mut items = HashMap::new();
+
+        // Smithed daggers, swords and platebodies (bronze, iron, steel, mithril)
+        let daggers = [(60, 4, 3), (63, 5, 4), (66, 8, 7), (69, 11, 10)];
+        let swords = [(61, 4, 5), (64, 6, 7), (67, 11, 12), (70, 16, 17)];
+        let platebodies = [(62, 15, 14), (65, 21, 20), (68, 32, 31), (71, 46, 44)];
+        for (item_id, attack, strength) in daggers {
+            items.insert(item_id, melee_weapon(0.8, attack, strength));
+        }
+        for (item_id, attack, strength) in swords {
+            items.insert(item_id, melee_weapon(1.0, attack, strength));
+        }
+        for (item_id, melee_defence, ranged_defence) in platebodies {
+            items.insert(item_id, armour(EquipmentSlot::Body, melee_defence, ranged_defence, -6, -30));
+        }
+
+        // Shortbows shoot faster than longbows
+        for (shortbow_id, ranged_attack) in [(91, 8), (93, 14), (95, 20), (97, 47)] {
+            items.insert(shortbow_id, bow(0.9, ranged_attack));
+            items.insert(shortbow_id + 1, bow(1.3, ranged_attack));
+        }
+
+        // Leather armour
+        items.insert(100, armour(EquipmentSlot::Hands, 1, 1, 1, 0));
+        items.insert(101, armour(EquipmentSlot::Feet, 1, 1, 1, 0));
+        items.insert(102, armour(EquipmentSlot::Body, 8, 9, 4, -2));
+
+        Self { items }
+    }
+}
+
+// Helpers to define weapons and armour
+fn melee_weapon(attack_speed: f32, attack: i32, strength: i32) -> EquipmentStats {
+    EquipmentStats {
+        slot: EquipmentSlot::Weapon,
+        attack_speed,
+        bonuses: CombatBonuses {
+            melee_attack: attack,
+            melee_strength: strength,
+            ..default()
+        },
+    }
+}
+
+fn bow(attack_speed: f32, ranged_attack: i32) -> EquipmentStats {
+    EquipmentStats {
+        slot: EquipmentSlot::Weapon,
+        attack_speed,
+        bonuses: CombatBonuses {
+            ranged_attack,
+            ..default()
+        },
+    }
+}
+
+fn armour(slot: EquipmentSlot, melee_defence: i32, ranged_defence: i32, magic_defence: i32, magic_attack: i32) -> EquipmentStats {
+    EquipmentStats {
+        slot,
+        attack_speed: 1.0,
+        bonuses: CombatBonuses {
+            magic_attack,
+            melee_defence,
+            ranged_defence,
+            magic_defence,
+            ..default()
+        },
+    }
+}
+
+// Items the player is wearing
+#[derive(Component, Default)]
+pub struct Equipment {
+    pub slots: HashMap<EquipmentSlot, u64>,
+}
+
+impl Equipment {
+    pub fn weapon(&self) -> Option<u64> {
+        self.slots.get(&EquipmentSlot::Weapon).copied()
+    }
+
+    // Attack delay multiplier of the wielded weapon (1.0 when unarmed)
+    pub fn attack_speed(&self, database: &EquipmentDatabase) -> f32 {
+        self.weapon()
+            .and_then(|item_id| database.items.get(&item_id))
+            .map_or(1.0, |stats| stats.attack_speed)
+    }
+
+    // Total bonuses of everything worn
+    pub fn bonuses(&self, database: &EquipmentDatabase) -> CombatBonuses {
+        self.slots.values()
+            .filter_map(|item_id| database.items.get(item_id))
+            .fold(CombatBonuses::default(), |total, stats| total + stats.bonuses)
+    }
+}
+
+// Event for taking off whatever is worn in a slot
+#[derive(Event)]
+pub struct UnequipItemEvent {
+    pub slot: EquipmentSlot,
+}
+
+// Use a wearable item alone to equip it, swapping out anything already in its slot
+fn equip_items(
+    mut events: EventReader<UseItemEvent>,
+    database: Res<EquipmentDatabase>,
+    mut player_query: Query<(&Inventory, &mut Equipment), With<Player>>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((inventory, mut equipment)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.target_item_id.is_some() || !inventory.items.contains_key(&event.item_id) {
+            continue;
+        }
+        let Some(stats) = database.items.get(&event.item_id) else {
+            continue;
+        };
+
+        // Equipping the last of a stack frees its slot for the item being swapped out;
+        // otherwise that item needs room of its own
+        let stacked = inventory.items.get(&event.item_id).is_some_and(|&quantity| quantity > 1);
+        let previous = equipment.slots.get(&stats.slot).copied();
+        if stacked && previous.is_some_and(|previous| !inventory.has_room_for(previous)) {
+            println!("You don't have enough inventory space to do that.");
+            continue;
+        }
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: event.item_id,
+            quantity: -1,
+        });
+        if let Some(previous) = equipment.slots.insert(stats.slot, event.item_id) {
+            inventory_events.send(InventoryUpdateEvent {
+                item_id: previous,
+                quantity: 1,
+            });
+        }
+    }
+}
+
+// Move an equipped item back into the inventory
+fn unequip_items(
+    mut events: EventReader<UnequipItemEvent>,
+    mut player_query: Query<(&Inventory, &mut Equipment), With<Player>>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((inventory, mut equipment)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let Some(&item_id) = equipment.slots.get(&event.slot) else {
+            continue;
+        };
+        if !inventory.has_room_for(item_id) {
+            println!("You don't have enough inventory space to do that.");
+            continue;
+        }
+
+        equipment.slots.remove(&event.slot);
+        inventory_events.send(InventoryUpdateEvent {
+            item_id,
+            quantity: 1,
+        });
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::OnceLock;
+
+// The single experience curve shared by every system that needs skill levels.
+// Follows RuneScape's formula: each level needs floor(level + 300 * 2^(level / 7)) / 4 more XP.
+
+// Highest real level a skill can reach
+pub const MAX_LEVEL: u32 = 99;
+
+// Highest virtual level, only used for display once a skill is past 99
+pub const MAX_VIRTUAL_LEVEL: u32 = 120;
+
+// Experience required for each level, indexed by level - 1 (level 1 needs 0 XP)
+fn experience_table() -> &'static [u32] {
+    static TABLE: OnceLock<Vec<u32>> = OnceLock::new();
+
+    TABLE.get_or_init(|| {
+        let mut table = Vec::with_capacity(MAX_VIRTUAL_LEVEL as usize);
+        let mut points = 0.0_f64;
+        table.push(0);
+
+        for level in 1..MAX_VIRTUAL_LEVEL {
+            points += (level as f64 + 300.0 * 2.0_f64.powf(level as f64 / 7.0)).floor();
+            table.push((points / 4.0).floor() as u32);
+        }
+
+        // Every level must need strictly more XP than the one before it
+        debug_assert!(table.windows(2).all(|pair| pair[0] < pair[1]), "experience curve must be monotonic");
+
+        table
+    })
+}
+
+// Total experience required to reach a level (clamped to 1..=MAX_VIRTUAL_LEVEL)
+pub fn experience_for_level(level: u32) -> u32 {
+    let level = level.clamp(1, MAX_VIRTUAL_LEVEL);
+    experience_table()[(level - 1) as usize]
+}
+
+// Level for an amount of experience, capped at MAX_LEVEL
+pub fn level_for_experience(experience: u32) -> u32 {
+    virtual_level_for_experience(experience).min(MAX_LEVEL)
+}
+
+// Level for an amount of experience, continuing past 99 up to MAX_VIRTUAL_LEVEL
+pub fn virtual_level_for_experience(experience: u32) -> u32 {
+    experience_table().partition_point(|&required| required <= experience) as u32
+}
+
+// Experience still needed for the next level, or None once the virtual cap is reached
+pub fn experience_to_next_level(experience: u32) -> Option<u32> {
+    let level = virtual_level_for_experience(experience);
+    if level >= MAX_VIRTUAL_LEVEL {
+        return None;
+    }
+
+    Some(experience_for_level(level + 1) - experience)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn experience_strictly_increases_with_level() {
+        for level in 1..MAX_VIRTUAL_LEVEL {
+            assert!(experience_for_level(level) < experience_for_level(level + 1), "level {} to {}", level, level + 1);
+        }
+    }
+
+    #[test]
+    fn levels_never_drop_as_experience_rises() {
+        // Every level boundary and the XP either side of it, in increasing order
+        let experience_values = (1..=MAX_VIRTUAL_LEVEL)
+            .map(experience_for_level)
+            .flat_map(|boundary| [boundary.saturating_sub(1), boundary, boundary + 1]);
+
+        let mut previous = (1, 1);
+        for experience in experience_values {
+            let current = (level_for_experience(experience), virtual_level_for_experience(experience));
+            assert!(current.0 >= previous.0 && current.1 >= previous.1, "level dropped at {} XP", experience);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn levels_never_drop_across_a_sweep_of_experience() {
+        let mut previous = (1, 1);
+        for experience in (0..=experience_for_level(MAX_VIRTUAL_LEVEL)).step_by(997) {
+            let current = (level_for_experience(experience), virtual_level_for_experience(experience));
+            assert!(current.0 >= previous.0 && current.1 >= previous.1, "level dropped at {} XP", experience);
+            previous = current;
+        }
+    }
+
+    #[test]
+    fn levels_change_exactly_at_their_boundary() {
+        for level in 2..=MAX_VIRTUAL_LEVEL {
+            let boundary = experience_for_level(level);
+            assert_eq!(virtual_level_for_experience(boundary - 1), level - 1);
+            assert_eq!(virtual_level_for_experience(boundary), level);
+        }
+    }
+
+    #[test]
+    fn level_round_trips_through_experience() {
+        for level in 1..=MAX_LEVEL {
+            assert_eq!(level_for_experience(experience_for_level(level)), level);
+        }
+    }
+
+    #[test]
+    fn level_is_capped_at_99() {
+        assert_eq!(level_for_experience(u32::MAX), MAX_LEVEL);
+    }
+
+    #[test]
+    fn virtual_level_reaches_120() {
+        assert_eq!(virtual_level_for_experience(experience_for_level(MAX_VIRTUAL_LEVEL)), MAX_VIRTUAL_LEVEL);
+        assert_eq!(virtual_level_for_experience(u32::MAX), MAX_VIRTUAL_LEVEL);
+    }
+
+    #[test]
+    fn no_experience_is_level_1() {
+        assert_eq!(level_for_experience(0), 1);
+    }
+}
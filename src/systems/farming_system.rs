@@ -0,0 +1,599 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use crate::shared::components::Skills;
+use crate::client::input::Player;
+use crate::client::terrain::{WorldObject, nearest_world_object};
+use crate::server::database::{load_json, save_json};
+use crate::server::world::server_time;
+use crate::systems::experience::level_for_experience;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, UseItemEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Item IDs of the farming supplies
+pub const COMPOST_ITEM_ID: u64 = 168;
+pub const SUPERCOMPOST_ITEM_ID: u64 = 169;
+pub const PLANT_CURE_ITEM_ID: u64 = 170;
+
+// Name of the save file the patches are stored in
+const PATCHES_SAVE_NAME: &str = "farming_patches";
+
+pub struct FarmingPlugin;
+
+impl Plugin for FarmingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FarmingDatabase>()
+           .init_resource::<FarmingPatches>()
+           .add_systems(Startup, (load_farming_patches, spawn_farming_patches))
+           .add_systems(Update, (
+               grow_crops,
+               use_item_on_patch,
+               handle_patch_interaction,
+               update_patch_visuals,
+               save_farming_patches,
+           ));
+    }
+}
+
+// Compost applied to a patch before or while growing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Compost {
+    #[default]
+    None,
+    Compost,
+    Supercompost,
+}
+
+impl Compost {
+    // Compost makes disease less likely...
+    pub fn disease_multiplier(&self) -> f32 {
+        match self {
+            Compost::None => 1.0,
+            Compost::Compost => 0.5,
+            Compost::Supercompost => 0.2,
+        }
+    }
+
+    // ...and increases the harvest
+    pub fn yield_bonus(&self) -> u32 {
+        match self {
+            Compost::None => 0,
+            Compost::Compost => 1,
+            Compost::Supercompost => 2,
+        }
+    }
+}
+
+// What is growing in a patch. Times are server_time() seconds, so growth carries on
+// while the player is offline and is caught up the next time the patch is checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PatchState {
+    pub seed_item_id: Option<u64>,
+    pub stage: u32,
+    pub stage_started_at: u64,
+    pub compost: Compost,
+    pub diseased_at: Option<u64>,
+    pub dead: bool,
+}
+
+impl PatchState {
+    pub fn is_empty(&self) -> bool {
+        self.seed_item_id.is_none()
+    }
+}
+
+// Every patch's state by patch ID, saved to disk whenever it changes
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct FarmingPatches {
+    pub patches: HashMap<u32, PatchState>,
+    #[serde(skip)]
+    pub dirty: bool,
+}
+
+// The kind of patch a crop has to be planted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchKind {
+    Allotment,
+    Herb,
+}
+
+impl PatchKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatchKind::Allotment => "an allotment",
+            PatchKind::Herb => "a herb patch",
+        }
+    }
+}
+
+// A crop that can be grown from a seed
+#[derive(Clone, Debug)]
+pub struct CropDefinition {
+    pub name: String,
+    pub patch_kind: PatchKind,
+    pub produce_item_id: u64,
+    pub level_requirement: u32,
+    pub planting_experience: u32,
+    pub harvest_experience: u32,
+    pub stages: u32,
+    // Real-time seconds per growth stage
+    pub stage_time: u64,
+    // Chance of catching disease at each new growth stage (before compost)
+    pub disease_chance: f32,
+    pub base_yield: (u32, u32),
+    pub color: Color,
+}
+
+// Resource for crops, keyed by seed item ID
+#[derive(Resource)]
+pub struct FarmingDatabase {
+    pub crops: HashMap<u64, CropDefinition>,
+}
+
+impl Default for FarmingDatabase {
+    fn default() -> Self {
+        let mut crops = HashMap::new();
+        insert_crop(&mut crops, 160, "Potato", PatchKind::Allotment, 165, 1, (8, 9), (4, 600), 0.1, (3, 6), Color::rgb(0.75, 0.6, 0.35));
+        insert_crop(&mut crops, 161, "Onion", PatchKind::Allotment, 166, 5, (9, 10), (4, 600), 0.1, (3, 6), Color::rgb(0.9, 0.8, 0.5));
+        insert_crop(&mut crops, 162, "Cabbage", PatchKind::Allotment, 167, 7, (10, 11), (4, 600), 0.1, (3, 6), Color::rgb(0.5, 0.8, 0.4));
+        insert_crop(&mut crops, 163, "Guam", PatchKind::Herb, 110, 9, (11, 12), (4, 1200), 0.15, (3, 5), Color::rgb(0.3, 0.6, 0.25));
+        insert_crop(&mut crops, 164, "Ranarr", PatchKind::Herb, 113, 32, (27, 30), (4, 1200), 0.15, (3, 5), Color::rgb(0.35, 0.5, 0.3));
+
+        Self { crops }
+    }
+}
+
+// Helper to add a crop to the database
+#[allow(clippy::too_many_arguments)]
+fn insert_crop(
+    crops: &mut HashMap<u64, CropDefinition>,
+    seed_item_id: u64,
+    name: &str,
+    patch_kind: PatchKind,
+    produce_item_id: u64,
+    level_requirement: u32,
+    (planting_experience, harvest_experience): (u32, u32),
+    (stages, stage_time): (u32, u64),
+    disease_chance: f32,
+    base_yield: (u32, u32),
+    color: Color,
+) {
+    crops.insert(seed_item_id, CropDefinition {
+        name: name.to_string(),
+        patch_kind,
+        produce_item_id,
+        level_requirement,
+        planting_experience,
+        harvest_experience,
+        stages,
+        stage_time,
+        disease_chance,
+        base_yield,
+        color,
+    });
+}
+
+// Links a patch in the world to its saved state
+#[derive(Component)]
+pub struct FarmingPatch {
+    pub patch_id: u32,
+    pub kind: PatchKind,
+    pub crop: Entity,
+}
+
+// Patches on the farm (patch_id, kind, position)
+const PATCH_LOCATIONS: [(u32, PatchKind, Vec3); 6] = [
+    (1, PatchKind::Allotment, Vec3::new(-6.0, 0.0, 14.0)),
+    (2, PatchKind::Allotment, Vec3::new(-9.0, 0.0, 14.0)),
+    (3, PatchKind::Allotment, Vec3::new(-6.0, 0.0, 17.0)),
+    (4, PatchKind::Allotment, Vec3::new(-9.0, 0.0, 17.0)),
+    (5, PatchKind::Herb, Vec3::new(-12.5, 0.0, 14.0)),
+    (6, PatchKind::Herb, Vec3::new(-12.5, 0.0, 17.0)),
+];
+
+// Advance a patch to the current time, rolling for disease at each new stage
+pub fn advance_patch(state: &mut PatchState, crop: &CropDefinition, now: u64, rng: &mut impl Rng) {
+    while !state.dead && state.stage < crop.stages {
+        // Diseased crops stop growing and die if left for another stage
+        if let Some(diseased_at) = state.diseased_at {
+            if now >= diseased_at + crop.stage_time {
+                state.dead = true;
+            }
+            break;
+        }
+
+        let next_stage_at = state.stage_started_at + crop.stage_time;
+        if now < next_stage_at {
+            break;
+        }
+
+        state.stage += 1;
+        state.stage_started_at = next_stage_at;
+
+        let disease_chance = crop.disease_chance * state.compost.disease_multiplier();
+        if state.stage < crop.stages && rng.gen_bool(disease_chance as f64) {
+            state.diseased_at = Some(next_stage_at);
+        }
+    }
+}
+
+// Restore patch state saved by a previous session
+fn load_farming_patches(mut patches: ResMut<FarmingPatches>) {
+    match load_json::<FarmingPatches>(PATCHES_SAVE_NAME) {
+        Ok(Some(saved)) => patches.patches = saved.patches,
+        Ok(None) => {},
+        Err(error) => println!("Failed to load farming patches: {}", error),
+    }
+}
+
+// Write patch state to disk after it changes
+fn save_farming_patches(mut patches: ResMut<FarmingPatches>) {
+    if !patches.dirty {
+        return;
+    }
+    patches.dirty = false;
+
+    if let Err(error) = save_json(PATCHES_SAVE_NAME, &*patches) {
+        println!("Failed to save farming patches: {}", error);
+    }
+}
+
+// Spawn the patch world objects, each with a crop model that changes as it grows
+fn spawn_farming_patches(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let soil_mesh = meshes.add(Mesh::from(shape::Box::new(2.4, 0.15, 2.4)));
+    let soil_material = materials.add(Color::rgb(0.35, 0.22, 0.12).into());
+    let crop_mesh = meshes.add(Mesh::from(shape::Box::new(1.8, 0.6, 1.8)));
+
+    for (patch_id, kind, position) in PATCH_LOCATIONS {
+        let crop = commands.spawn(PbrBundle {
+            mesh: crop_mesh.clone(),
+            material: materials.add(Color::rgb(0.3, 0.6, 0.2).into()),
+            transform: Transform::from_xyz(0.0, 0.075, 0.0),
+            visibility: Visibility::Hidden,
+            ..default()
+        }).id();
+
+        commands.spawn((
+            PbrBundle {
+                mesh: soil_mesh.clone(),
+                material: soil_material.clone(),
+                transform: Transform::from_translation(position + Vec3::Y * 0.075),
+                ..default()
+            },
+            WorldObject::FarmingPatch,
+            FarmingPatch { patch_id, kind, crop },
+        )).add_child(crop);
+    }
+}
+
+// Catch every patch up with the server clock
+fn grow_crops(
+    database: Res<FarmingDatabase>,
+    mut patches: ResMut<FarmingPatches>,
+    mut check_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+) {
+    // Stages last minutes, so there's no need to check every frame
+    let timer = check_timer.get_or_insert_with(|| Timer::from_seconds(1.0, TimerMode::Repeating));
+    timer.tick(time.delta());
+    if !timer.just_finished() {
+        return;
+    }
+
+    let now = server_time();
+    let mut rng = rand::thread_rng();
+    let mut changed = false;
+
+    for state in patches.patches.values_mut() {
+        let Some(crop) = state.seed_item_id.and_then(|seed| database.crops.get(&seed)) else {
+            continue;
+        };
+
+        let before = (state.stage, state.diseased_at, state.dead);
+        advance_patch(state, crop, now, &mut rng);
+        changed |= before != (state.stage, state.diseased_at, state.dead);
+    }
+
+    if changed {
+        patches.dirty = true;
+    }
+}
+
+// Use seeds, compost or plant cure while standing at a patch
+#[allow(clippy::too_many_arguments)]
+fn use_item_on_patch(
+    mut commands: Commands,
+    mut events: EventReader<UseItemEvent>,
+    database: Res<FarmingDatabase>,
+    mut patches: ResMut<FarmingPatches>,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    patch_query: Query<&FarmingPatch>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+
+    for event in events.read() {
+        if event.target_item_id.is_some() || !inventory.items.contains_key(&event.item_id) {
+            continue;
+        }
+        let is_farming_item = database.crops.contains_key(&event.item_id)
+            || matches!(event.item_id, COMPOST_ITEM_ID | SUPERCOMPOST_ITEM_ID | PLANT_CURE_ITEM_ID);
+        if !is_farming_item {
+            continue;
+        }
+
+        let Some(patch) = nearest_world_object(player_transform.translation, object_query.iter(), |object| *object == WorldObject::FarmingPatch)
+            .and_then(|(entity, _)| patch_query.get(entity).ok())
+        else {
+            println!("You need to be standing at a farming patch to use that.");
+            continue;
+        };
+        let state = patches.patches.entry(patch.patch_id).or_default();
+
+        match event.item_id {
+            COMPOST_ITEM_ID | SUPERCOMPOST_ITEM_ID => {
+                if state.dead || state.compost != Compost::None {
+                    println!("This patch doesn't need any more compost.");
+                    continue;
+                }
+                state.compost = if event.item_id == COMPOST_ITEM_ID { Compost::Compost } else { Compost::Supercompost };
+                inventory_events.send(InventoryUpdateEvent { item_id: event.item_id, quantity: -1 });
+                skill_events.send(SkillExperienceEvent { skill_name: "farming".to_string(), experience: 18 });
+                spawn_floating_text(&mut commands, text_position, "You treat the patch with compost.".to_string(), Color::rgb(0.6, 0.9, 0.4));
+            },
+            PLANT_CURE_ITEM_ID => {
+                if state.diseased_at.is_none() || state.dead {
+                    println!("There's nothing to cure in this patch.");
+                    continue;
+                }
+                // Growth picks up from the moment the crop is cured
+                state.diseased_at = None;
+                state.stage_started_at = server_time();
+                inventory_events.send(InventoryUpdateEvent { item_id: event.item_id, quantity: -1 });
+                spawn_floating_text(&mut commands, text_position, "You cure the diseased crop.".to_string(), Color::rgb(0.6, 0.9, 0.4));
+            },
+            seed_item_id => {
+                let Some(crop) = database.crops.get(&seed_item_id) else {
+                    continue;
+                };
+                if crop.patch_kind != patch.kind {
+                    println!("{} seeds can only be planted in {}.", crop.name, crop.patch_kind.name());
+                    continue;
+                }
+                if !state.is_empty() {
+                    println!("Something is already growing in this patch.");
+                    continue;
+                }
+                if level_for_experience(skills.farming) < crop.level_requirement {
+                    let message = format!("You need a farming level of {} to plant that.", crop.level_requirement);
+                    println!("{}", message);
+                    spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.3, 0.3));
+                    continue;
+                }
+
+                *state = PatchState {
+                    seed_item_id: Some(seed_item_id),
+                    stage: 0,
+                    stage_started_at: server_time(),
+                    compost: state.compost,
+                    diseased_at: None,
+                    dead: false,
+                };
+                inventory_events.send(InventoryUpdateEvent { item_id: seed_item_id, quantity: -1 });
+                skill_events.send(SkillExperienceEvent { skill_name: "farming".to_string(), experience: crop.planting_experience });
+                spawn_floating_text(&mut commands, text_position, format!("You plant a {} seed.", crop.name.to_lowercase()), Color::rgb(0.6, 0.9, 0.4));
+            },
+        }
+
+        patches.dirty = true;
+    }
+}
+
+// Press F at a patch to harvest it, clear dead crops, or check on its growth
+#[allow(clippy::too_many_arguments)]
+fn handle_patch_interaction(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    database: Res<FarmingDatabase>,
+    mut patches: ResMut<FarmingPatches>,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    patch_query: Query<&FarmingPatch>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Some(patch) = interact_events.read().last().and_then(|event| patch_query.get(event.target).ok()) else {
+        return;
+    };
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+
+    let Some(state) = patches.patches.get_mut(&patch.patch_id) else {
+        spawn_floating_text(&mut commands, text_position, "The patch is empty.".to_string(), Color::WHITE);
+        return;
+    };
+    let Some(crop) = state.seed_item_id.and_then(|seed| database.crops.get(&seed)) else {
+        spawn_floating_text(&mut commands, text_position, "The patch is empty.".to_string(), Color::WHITE);
+        return;
+    };
+
+    let message = if state.dead {
+        *state = PatchState::default();
+        "You clear away the dead plants.".to_string()
+    } else if state.diseased_at.is_some() {
+        format!("The {} crop is diseased. Use plant cure on it.", crop.name.to_lowercase())
+    } else if state.stage < crop.stages {
+        format!("The {} crop is growing (stage {}/{}).", crop.name.to_lowercase(), state.stage + 1, crop.stages)
+    } else {
+        if !inventory.has_room_for(crop.produce_item_id) {
+            println!("You don't have enough inventory space.");
+            return;
+        }
+
+        // Higher levels and better compost give bigger harvests
+        let farming_level = level_for_experience(skills.farming);
+        let (min_yield, max_yield) = crop.base_yield;
+        let amount = rand::thread_rng().gen_range(min_yield..=max_yield) + farming_level / 15 + state.compost.yield_bonus();
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: crop.produce_item_id,
+            quantity: amount as i32,
+        });
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "farming".to_string(),
+            experience: crop.harvest_experience * amount,
+        });
+
+        let message = format!("You harvest {} {}.", amount, crop.name.to_lowercase());
+        *state = PatchState::default();
+        message
+    };
+
+    patches.dirty = true;
+    spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.6, 0.9, 0.4));
+}
+
+// Show each patch's crop growing, diseased, dead or ready to harvest
+fn update_patch_visuals(
+    database: Res<FarmingDatabase>,
+    patches: Res<FarmingPatches>,
+    patch_query: Query<&FarmingPatch>,
+    mut crop_query: Query<(&mut Transform, &mut Visibility, &Handle<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !patches.is_changed() {
+        return;
+    }
+
+    for patch in patch_query.iter() {
+        let Ok((mut transform, mut visibility, material)) = crop_query.get_mut(patch.crop) else {
+            continue;
+        };
+        let state = patches.patches.get(&patch.patch_id);
+        let crop = state.and_then(|state| state.seed_item_id).and_then(|seed| database.crops.get(&seed));
+
+        let (Some(state), Some(crop)) = (state, crop) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        *visibility = Visibility::Inherited;
+        let growth = (state.stage + 1) as f32 / (crop.stages + 1) as f32;
+        transform.scale = Vec3::new(1.0, growth, 1.0);
+        transform.translation.y = 0.075 + 0.3 * growth;
+
+        if let Some(material) = materials.get_mut(material) {
+            material.base_color = if state.dead {
+                Color::rgb(0.35, 0.3, 0.25)
+            } else if state.diseased_at.is_some() {
+                Color::rgb(0.6, 0.55, 0.2)
+            } else {
+                crop.color
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const STAGE_TIME: u64 = 600;
+    const PLANTED_AT: u64 = 1_000;
+
+    fn crop(disease_chance: f32) -> CropDefinition {
+        CropDefinition {
+            name: "Potato".to_string(),
+            patch_kind: PatchKind::Allotment,
+            produce_item_id: 165,
+            level_requirement: 1,
+            planting_experience: 8,
+            harvest_experience: 9,
+            stages: 4,
+            stage_time: STAGE_TIME,
+            disease_chance,
+            base_yield: (3, 6),
+            color: Color::WHITE,
+        }
+    }
+
+    fn planted() -> PatchState {
+        PatchState {
+            seed_item_id: Some(160),
+            stage_started_at: PLANTED_AT,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn healthy_crops_grow_one_stage_per_stage_time() {
+        let crop = crop(0.0);
+        let mut state = planted();
+        let mut rng = StdRng::seed_from_u64(37);
+
+        advance_patch(&mut state, &crop, PLANTED_AT + STAGE_TIME - 1, &mut rng);
+        assert_eq!(state.stage, 0);
+
+        // Partway through a stage, growth is caught up to the last stage boundary
+        advance_patch(&mut state, &crop, PLANTED_AT + STAGE_TIME * 5 / 2, &mut rng);
+        assert_eq!(state.stage, 2);
+        assert_eq!(state.stage_started_at, PLANTED_AT + STAGE_TIME * 2);
+
+        // Long offline, the crop finishes growing and goes no further
+        advance_patch(&mut state, &crop, PLANTED_AT + STAGE_TIME * 100, &mut rng);
+        assert_eq!(state.stage, crop.stages);
+        assert!(state.diseased_at.is_none() && !state.dead);
+    }
+
+    #[test]
+    fn diseased_crops_stop_growing_then_die_after_another_stage() {
+        let crop = crop(1.0);
+        let mut state = planted();
+        let mut rng = StdRng::seed_from_u64(37);
+
+        advance_patch(&mut state, &crop, PLANTED_AT + STAGE_TIME * 3 / 2, &mut rng);
+        assert_eq!(state.stage, 1);
+        assert_eq!(state.diseased_at, Some(PLANTED_AT + STAGE_TIME));
+        assert!(!state.dead);
+
+        advance_patch(&mut state, &crop, PLANTED_AT + STAGE_TIME * 2, &mut rng);
+        assert_eq!(state.stage, 1);
+        assert!(state.dead);
+    }
+
+    #[test]
+    fn catching_up_in_one_step_matches_checking_every_stage() {
+        let crop = crop(0.3);
+
+        for seed in 0..50 {
+            let mut caught_up = planted();
+            advance_patch(&mut caught_up, &crop, PLANTED_AT + STAGE_TIME * 10, &mut StdRng::seed_from_u64(seed));
+
+            let mut checked = planted();
+            let mut rng = StdRng::seed_from_u64(seed);
+            for stages_passed in 1..=10 {
+                advance_patch(&mut checked, &crop, PLANTED_AT + STAGE_TIME * stages_passed, &mut rng);
+            }
+
+            assert_eq!(
+                (caught_up.stage, caught_up.diseased_at, caught_up.dead),
+                (checked.stage, checked.diseased_at, checked.dead),
+                "seed {}", seed,
+            );
+        }
+    }
+}
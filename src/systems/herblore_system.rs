@@ -0,0 +1,260 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::shared::components::Skills;
+use crate::shared::entities::NPC;
+use crate::client::input::Player;
+use crate::systems::combat_system::{DamageEvent, Enemy};
+use crate::systems::experience::level_for_experience;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, UseItemEvent};
+use crate::systems::skills_system::spawn_floating_text;
+
+// Item ID of the empty vial left behind after the last dose
+pub const VIAL_ITEM_ID: u64 = 146;
+
+// Skills that restore potions bring back up after being drained
+const RESTORABLE_SKILLS: [&str; 5] = ["attack", "strength", "defense", "ranged", "magic"];
+
+// Monsters whose attacks drain one of the player's skills (NPC name, skill, levels per hit)
+const DRAINING_ATTACKS: [(&str, &str, i32); 4] = [
+    ("Banshee", "attack", 1),
+    ("Rockslug", "defense", 1),
+    ("Pyrefiend", "magic", 1),
+    ("Black Demon", "strength", 2),
+];
+
+pub struct HerblorePlugin;
+
+impl Plugin for HerblorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PotionDatabase>()
+           .add_systems(Update, (
+               drink_potion,
+               drain_skills_on_hit,
+               decay_skill_boosts,
+           ));
+    }
+}
+
+// Temporary changes to skill levels, positive for boosts and negative for drains
+#[derive(Component)]
+pub struct SkillBoosts {
+    pub levels: HashMap<String, i32>,
+    // Every tick of this timer moves each boost or drain one level back towards zero
+    pub decay_timer: Timer,
+}
+
+impl Default for SkillBoosts {
+    fn default() -> Self {
+        Self {
+            levels: HashMap::new(),
+            decay_timer: Timer::from_seconds(60.0, TimerMode::Repeating),
+        }
+    }
+}
+
+impl SkillBoosts {
+    pub fn boost(&self, skill_name: &str) -> i32 {
+        self.levels.get(skill_name).copied().unwrap_or(0)
+    }
+}
+
+// Current level of a skill including any boost or drain
+pub fn boosted_level(skills: &Skills, boosts: Option<&SkillBoosts>, skill_name: &str) -> u32 {
+    let level = skills.experience(skill_name).map(level_for_experience).unwrap_or(1);
+    let boost = boosts.map_or(0, |boosts| boosts.boost(skill_name));
+    (level as i32 + boost).max(0) as u32
+}
+
+// What drinking a dose of a potion does
+#[derive(Clone, Debug)]
+pub enum PotionEffect {
+    // Raise skills above their real level by flat + percent of the level
+    Boost { skills: Vec<&'static str>, flat: u32, percent: f32 },
+    // Bring drained skills back towards their real level by flat + percent of the level
+    Restore { skills: Vec<&'static str>, flat: u32, percent: f32 },
+}
+
+// A drinkable potion and the item IDs of each of its doses
+#[derive(Clone, Debug)]
+pub struct PotionDefinition {
+    pub name: String,
+    // Highest dose first, e.g. [potion(4), potion(3), potion(2), potion(1)]
+    pub doses: Vec<u64>,
+    pub effect: PotionEffect,
+}
+
+impl PotionDefinition {
+    // The item left after drinking a dose (the next lower dose, or an empty vial)
+    pub fn next_item(&self, item_id: u64) -> u64 {
+        let index = self.doses.iter().position(|&dose| dose == item_id).unwrap_or(self.doses.len());
+        self.doses.get(index + 1).copied().unwrap_or(VIAL_ITEM_ID)
+    }
+}
+
+// Resource for drinkable potions
+#[derive(Resource)]
+pub struct PotionDatabase {
+    pub potions: Vec<PotionDefinition>,
+}
+
+impl Default for PotionDatabase {
+    fn default() -> Self {
+        let potions = vec![
+            PotionDefinition {
+                name: "Attack potion".to_string(),
+                doses: vec![130, 131, 132, 133],
+                effect: PotionEffect::Boost { skills: vec!["attack"], flat: 3, percent: 0.1 },
+            },
+            PotionDefinition {
+                name: "Strength potion".to_string(),
+                doses: vec![134, 135, 136, 137],
+                effect: PotionEffect::Boost { skills: vec!["strength"], flat: 3, percent: 0.1 },
+            },
+            PotionDefinition {
+                name: "Restore potion".to_string(),
+                doses: vec![138, 139, 140, 141],
+                effect: PotionEffect::Restore { skills: RESTORABLE_SKILLS.to_vec(), flat: 10, percent: 0.3 },
+            },
+            PotionDefinition {
+                name: "Defence potion".to_string(),
+                doses: vec![142, 143, 144, 145],
+                effect: PotionEffect::Boost { skills: vec!["defense"], flat: 3, percent: 0.1 },
+            },
+        ];
+
+        Self { potions }
+    }
+}
+
+impl PotionDatabase {
+    pub fn potion_for_item(&self, item_id: u64) -> Option<&PotionDefinition> {
+        self.potions.iter().find(|potion| potion.doses.contains(&item_id))
+    }
+}
+
+// Use a potion alone to drink a dose of it
+fn drink_potion(
+    mut commands: Commands,
+    mut events: EventReader<UseItemEvent>,
+    database: Res<PotionDatabase>,
+    mut player_query: Query<(&Transform, &Skills, &Inventory, &mut SkillBoosts), With<Player>>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((player_transform, skills, inventory, mut boosts)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.target_item_id.is_some() || !inventory.items.contains_key(&event.item_id) {
+            continue;
+        }
+        let Some(potion) = database.potion_for_item(event.item_id) else {
+            continue;
+        };
+
+        match &potion.effect {
+            PotionEffect::Boost { skills: boosted_skills, flat, percent } => {
+                for skill_name in boosted_skills.iter() {
+                    let level = skills.experience(skill_name).map(level_for_experience).unwrap_or(1);
+                    let amount = (*flat + (level as f32 * percent) as u32) as i32;
+                    // Drinking again refreshes a boost but never stacks it
+                    let current = boosts.levels.entry(skill_name.to_string()).or_insert(0);
+                    *current = (*current).max(amount);
+                }
+            },
+            PotionEffect::Restore { skills: restored_skills, flat, percent } => {
+                for skill_name in restored_skills.iter() {
+                    let level = skills.experience(skill_name).map(level_for_experience).unwrap_or(1);
+                    let amount = (*flat + (level as f32 * percent) as u32) as i32;
+                    if let Some(current) = boosts.levels.get_mut(*skill_name) {
+                        if *current < 0 {
+                            *current = (*current + amount).min(0);
+                        }
+                    }
+                }
+            },
+        }
+        boosts.levels.retain(|_, boost| *boost != 0);
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: event.item_id,
+            quantity: -1,
+        });
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: potion.next_item(event.item_id),
+            quantity: 1,
+        });
+
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("You drink some of your {}.", potion.name.to_lowercase()),
+            Color::rgb(0.4, 0.9, 0.6),
+        );
+    }
+}
+
+// Some monsters drain a skill with every hit that lands, down to at most half its real level
+fn drain_skills_on_hit(
+    mut commands: Commands,
+    mut events: EventReader<DamageEvent>,
+    npc_query: Query<&NPC, With<Enemy>>,
+    mut player_query: Query<(Entity, &Transform, &Skills, &mut SkillBoosts), With<Player>>,
+) {
+    let Ok((player_entity, player_transform, skills, mut boosts)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        // Misses and blocked attacks don't drain anything
+        if event.target != player_entity || event.amount == 0 {
+            continue;
+        }
+        let Some(npc) = event.attacker.and_then(|attacker| npc_query.get(attacker).ok()) else {
+            continue;
+        };
+        let Some(&(_, skill_name, amount)) = DRAINING_ATTACKS.iter().find(|(name, _, _)| *name == npc.name) else {
+            continue;
+        };
+
+        // Drains eat into any boost first
+        let level = skills.experience(skill_name).map(level_for_experience).unwrap_or(1);
+        let lowest = -(level as i32 / 2);
+        let current = boosts.levels.entry(skill_name.to_string()).or_insert(0);
+        if *current <= lowest {
+            continue;
+        }
+        *current = (*current - amount).max(lowest);
+        boosts.levels.retain(|_, boost| *boost != 0);
+
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("The {} drains your {}!", npc.name, skill_name),
+            Color::rgb(0.9, 0.3, 0.3),
+        );
+    }
+}
+
+// Move boosts and drains one level back towards the real level each minute
+fn decay_skill_boosts(
+    mut query: Query<&mut SkillBoosts>,
+    time: Res<Time>,
+) {
+    for mut boosts in query.iter_mut() {
+        if boosts.levels.is_empty() {
+            boosts.decay_timer.reset();
+            continue;
+        }
+
+        boosts.decay_timer.tick(time.delta());
+        if !boosts.decay_timer.just_finished() {
+            continue;
+        }
+
+        for boost in boosts.levels.values_mut() {
+            *boost -= boost.signum();
+        }
+        boosts.levels.retain(|_, boost| *boost != 0);
+    }
+}
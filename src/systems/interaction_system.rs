@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use crate::client::input::Player;
+use crate::client::terrain::{ResourceNodeType, WorldObject, WORLD_OBJECT_RANGE};
+use crate::systems::loot_system::GroundItem;
+use crate::systems::resources::DepletedResource;
+use crate::systems::slayer_system::SlayerMaster;
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<InteractEvent>()
+           .add_systems(Update, interact_with_nearest);
+    }
+}
+
+// Resource nodes are big, so they can be reached from a little further away than other things
+const RESOURCE_RANGE: f32 = 3.0;
+
+// Sent when the player presses F, naming the single closest thing they interact with.
+// Each system handles the targets it owns and ignores the rest.
+#[derive(Event)]
+pub struct InteractEvent {
+    pub target: Entity,
+}
+
+// World objects that F interacts with; the rest are used with items or their own keys
+fn interacts_on_f(object: &WorldObject) -> bool {
+    matches!(
+        object,
+        WorldObject::AgilityObstacle
+            | WorldObject::FarmingPatch
+            | WorldObject::RuneAltar
+            | WorldObject::PrayerAltar
+            | WorldObject::Gravestone
+    )
+}
+
+// Press F to interact with whatever is closest: gather a resource, pick up an item,
+// use an altar or obstacle, tend a patch, talk to a slayer master or reclaim a gravestone
+#[allow(clippy::type_complexity)]
+fn interact_with_nearest(
+    keyboard_input: Res<Input<KeyCode>>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    resource_query: Query<(Entity, &Transform), (With<ResourceNodeType>, Without<DepletedResource>)>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    master_query: Query<(Entity, &Transform), With<SlayerMaster>>,
+    ground_item_query: Query<(Entity, &Transform, &GroundItem)>,
+    mut interact_events: EventWriter<InteractEvent>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F) {
+        return;
+    }
+    let Ok((player_entity, player_transform)) = player_query.get_single() else {
+        return;
+    };
+    let position = player_transform.translation;
+
+    let resources = resource_query.iter()
+        .map(|(entity, transform)| (entity, transform, RESOURCE_RANGE));
+    let objects = object_query.iter()
+        .filter(|(_, _, object)| interacts_on_f(object))
+        .map(|(entity, transform, _)| (entity, transform, WORLD_OBJECT_RANGE));
+    let masters = master_query.iter()
+        .map(|(entity, transform)| (entity, transform, WORLD_OBJECT_RANGE));
+    let ground_items = ground_item_query.iter()
+        .filter(|(_, _, ground_item)| ground_item.visible_to(player_entity))
+        .map(|(entity, transform, _)| (entity, transform, WORLD_OBJECT_RANGE));
+
+    let nearest = resources.chain(objects).chain(masters).chain(ground_items)
+        .map(|(entity, transform, range)| (entity, transform.translation.distance(position), range))
+        .filter(|(_, distance, range)| distance <= range)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    match nearest {
+        Some((target, _, _)) => {
+            interact_events.send(InteractEvent { target });
+        },
+        None => println!("There's nothing here to interact with."),
+    }
+}
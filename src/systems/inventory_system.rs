@@ -1,6 +1,5 @@
 use bevy::prelude::*;
 use crate::client::input::Player;
-use crate::client::terrain::ResourceNodeType;
 use std::collections::HashMap;
 
 pub struct InventoryPlugin;
@@ -9,6 +8,7 @@ impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ItemDatabase>()
            .add_event::<InventoryUpdateEvent>()
+           .add_event::<UseItemEvent>()
            .add_systems(Update, (
                handle_inventory_updates,
                update_inventory_ui,
@@ -24,6 +24,13 @@ pub struct Inventory {
     pub gold: u32,
 }
 
+impl Inventory {
+    // Whether an item can be added without needing a slot that isn't there
+    pub fn has_room_for(&self, item_id: u64) -> bool {
+        self.items.contains_key(&item_id) || (self.items.len() as u32) < self.capacity
+    }
+}
+
 // Event for inventory updates
 #[derive(Event)]
 pub struct InventoryUpdateEvent {
@@ -31,10 +38,35 @@ pub struct InventoryUpdateEvent {
     pub quantity: i32, // Positive for add, negative for remove
 }
 
+// Event for using an item, alone or on another item (mirrors ClientMessage::UseItem)
+#[derive(Event)]
+pub struct UseItemEvent {
+    pub item_id: u64,
+    pub target_item_id: Option<u64>,
+}
+
 // Resource for item database
 #[derive(Resource)]
 pub struct ItemDatabase {
     pub items: HashMap<u64, ItemDefinition>,
+    // Inventory icons for items that don't use their type's default
+    pub icons: HashMap<u64, &'static str>,
+}
+
+impl ItemDatabase {
+    // The icon shown for an item in the inventory
+    pub fn icon(&self, item_id: u64) -> &'static str {
+        if let Some(&icon) = self.icons.get(&item_id) {
+            return icon;
+        }
+        match self.items.get(&item_id).map(|item| &item.item_type) {
+            Some(ItemType::Food) => "🐟",
+            Some(ItemType::Tool) => "⛏",
+            Some(ItemType::Equipment) => "⚔",
+            Some(ItemType::Consumable) => "🧪",
+            _ => "📦",
+        }
+    }
 }
 
 impl Default for ItemDatabase {
@@ -62,7 +94,7 @@ impl Default for ItemDatabase {
 
         items.insert(3, ItemDefinition {
             id: 3,
-            name: "Ore".to_string(),
+            name: "Copper ore".to_string(),
             description: "Metal ore that can be smelted.".to_string(),
             stackable: true,
             value: 20,
@@ -71,14 +103,170 @@ impl Default for ItemDatabase {
 
         items.insert(4, ItemDefinition {
             id: 4,
-            name: "Fish".to_string(),
+            name: "Raw shrimps".to_string(),
             description: "A fresh fish.".to_string(),
             stackable: true,
             value: 15,
             item_type: ItemType::Food,
         });
 
-        Self { items }
+        // Higher tier logs
+        insert_item(&mut items, 10, "Oak logs", "Logs cut from an oak tree.", true, 20, ItemType::Resource);
+        insert_item(&mut items, 11, "Willow logs", "Logs cut from a willow tree.", true, 40, ItemType::Resource);
+        insert_item(&mut items, 12, "Yew logs", "Logs cut from a yew tree.", true, 160, ItemType::Resource);
+
+        // Ores
+        insert_item(&mut items, 13, "Tin ore", "Metal ore that can be smelted.", true, 20, ItemType::Resource);
+        insert_item(&mut items, 14, "Iron ore", "Metal ore that can be smelted.", true, 50, ItemType::Resource);
+        insert_item(&mut items, 15, "Coal", "Fuel for smelting stronger metals.", true, 90, ItemType::Resource);
+        insert_item(&mut items, 16, "Mithril ore", "A light, strong metal ore.", true, 160, ItemType::Resource);
+
+        // Fish
+        insert_item(&mut items, 17, "Raw trout", "A fresh trout.", true, 30, ItemType::Food);
+        insert_item(&mut items, 18, "Raw lobster", "A fresh lobster.", true, 100, ItemType::Food);
+
+        // Gathering tools
+        insert_item(&mut items, 20, "Bronze axe", "A woodcutter's axe.", false, 16, ItemType::Tool);
+        insert_item(&mut items, 21, "Iron axe", "A woodcutter's axe.", false, 56, ItemType::Tool);
+        insert_item(&mut items, 22, "Steel axe", "A woodcutter's axe.", false, 200, ItemType::Tool);
+        insert_item(&mut items, 23, "Mithril axe", "A woodcutter's axe.", false, 520, ItemType::Tool);
+        insert_item(&mut items, 24, "Bronze pickaxe", "Used for mining.", false, 16, ItemType::Tool);
+        insert_item(&mut items, 25, "Iron pickaxe", "Used for mining.", false, 56, ItemType::Tool);
+        insert_item(&mut items, 26, "Steel pickaxe", "Used for mining.", false, 200, ItemType::Tool);
+        insert_item(&mut items, 27, "Mithril pickaxe", "Used for mining.", false, 520, ItemType::Tool);
+        insert_item(&mut items, 28, "Small fishing net", "Useful for catching small fish.", false, 5, ItemType::Tool);
+        insert_item(&mut items, 29, "Fishing rod", "Useful for catching trout.", false, 5, ItemType::Tool);
+        insert_item(&mut items, 30, "Lobster pot", "Useful for catching lobsters.", false, 20, ItemType::Tool);
+        insert_item(&mut items, 31, "Tinderbox", "Useful for lighting a fire.", false, 1, ItemType::Tool);
+        insert_item(&mut items, 32, "Hammer", "Good for hitting things.", false, 1, ItemType::Tool);
+        insert_item(&mut items, 33, "Knife", "A dangerous looking knife.", false, 6, ItemType::Tool);
+        insert_item(&mut items, 34, "Needle", "Used with leather to make clothes.", false, 1, ItemType::Tool);
+        insert_item(&mut items, 35, "Chisel", "Good for detailed crafting.", false, 1, ItemType::Tool);
+
+        // Cooked and burnt food
+        insert_item(&mut items, 40, "Shrimps", "Some nicely cooked shrimps.", false, 20, ItemType::Food);
+        insert_item(&mut items, 41, "Burnt shrimps", "Oops!", false, 1, ItemType::Resource);
+        insert_item(&mut items, 42, "Trout", "Some nicely cooked trout.", false, 40, ItemType::Food);
+        insert_item(&mut items, 43, "Burnt trout", "Oops!", false, 1, ItemType::Resource);
+        insert_item(&mut items, 44, "Lobster", "This looks tricky to eat.", false, 150, ItemType::Food);
+        insert_item(&mut items, 45, "Burnt lobster", "Oops!", false, 1, ItemType::Resource);
+
+        // Metal bars
+        insert_item(&mut items, 50, "Bronze bar", "It's a bar of bronze.", true, 8, ItemType::Resource);
+        insert_item(&mut items, 51, "Iron bar", "It's a bar of iron.", true, 28, ItemType::Resource);
+        insert_item(&mut items, 52, "Steel bar", "It's a bar of steel.", true, 100, ItemType::Resource);
+        insert_item(&mut items, 53, "Mithril bar", "It's a bar of mithril.", true, 300, ItemType::Resource);
+
+        // Smithed weapons and armour
+        insert_item(&mut items, 60, "Bronze dagger", "Short but pointy.", false, 10, ItemType::Equipment);
+        insert_item(&mut items, 61, "Bronze sword", "A razor-sharp sword.", false, 26, ItemType::Equipment);
+        insert_item(&mut items, 62, "Bronze platebody", "Provides excellent protection.", false, 160, ItemType::Equipment);
+        insert_item(&mut items, 63, "Iron dagger", "Short but pointy.", false, 35, ItemType::Equipment);
+        insert_item(&mut items, 64, "Iron sword", "A razor-sharp sword.", false, 91, ItemType::Equipment);
+        insert_item(&mut items, 65, "Iron platebody", "Provides excellent protection.", false, 560, ItemType::Equipment);
+        insert_item(&mut items, 66, "Steel dagger", "Short but pointy.", false, 125, ItemType::Equipment);
+        insert_item(&mut items, 67, "Steel sword", "A razor-sharp sword.", false, 325, ItemType::Equipment);
+        insert_item(&mut items, 68, "Steel platebody", "Provides excellent protection.", false, 2000, ItemType::Equipment);
+        insert_item(&mut items, 69, "Mithril dagger", "Short but pointy.", false, 325, ItemType::Equipment);
+        insert_item(&mut items, 70, "Mithril sword", "A razor-sharp sword.", false, 845, ItemType::Equipment);
+        insert_item(&mut items, 71, "Mithril platebody", "Provides excellent protection.", false, 5200, ItemType::Equipment);
+
+        // Crafting materials and gems
+        insert_item(&mut items, 83, "Leather", "It's a piece of leather.", true, 1, ItemType::Resource);
+        insert_item(&mut items, 84, "Uncut sapphire", "An uncut sapphire.", true, 25, ItemType::Resource);
+        insert_item(&mut items, 85, "Uncut emerald", "An uncut emerald.", true, 50, ItemType::Resource);
+        insert_item(&mut items, 86, "Uncut ruby", "An uncut ruby.", true, 100, ItemType::Resource);
+        insert_item(&mut items, 87, "Sapphire", "This looks valuable.", true, 50, ItemType::Resource);
+        insert_item(&mut items, 88, "Emerald", "This looks valuable.", true, 100, ItemType::Resource);
+        insert_item(&mut items, 89, "Ruby", "This looks valuable.", true, 200, ItemType::Resource);
+
+        // Fletched bows and arrow shafts
+        insert_item(&mut items, 90, "Arrow shaft", "A wooden arrow shaft.", true, 1, ItemType::Resource);
+        insert_item(&mut items, 91, "Shortbow", "A nice sturdy bow.", false, 50, ItemType::Equipment);
+        insert_item(&mut items, 92, "Longbow", "A nice sturdy bow.", false, 80, ItemType::Equipment);
+        insert_item(&mut items, 93, "Oak shortbow", "A shortbow made out of oak.", false, 100, ItemType::Equipment);
+        insert_item(&mut items, 94, "Oak longbow", "A longbow made out of oak.", false, 160, ItemType::Equipment);
+        insert_item(&mut items, 95, "Willow shortbow", "A shortbow made out of willow.", false, 200, ItemType::Equipment);
+        insert_item(&mut items, 96, "Willow longbow", "A longbow made out of willow.", false, 320, ItemType::Equipment);
+        insert_item(&mut items, 97, "Yew shortbow", "A shortbow made out of yew.", false, 800, ItemType::Equipment);
+        insert_item(&mut items, 98, "Yew longbow", "A longbow made out of yew.", false, 1280, ItemType::Equipment);
+
+        // Leather armour
+        insert_item(&mut items, 100, "Leather gloves", "Thin leather gloves.", false, 6, ItemType::Equipment);
+        insert_item(&mut items, 101, "Leather boots", "Comfortable leather boots.", false, 6, ItemType::Equipment);
+        insert_item(&mut items, 102, "Leather body", "Better than no armour!", false, 21, ItemType::Equipment);
+
+        // Herbs, vials and secondaries
+        insert_item(&mut items, 110, "Guam leaf", "A herb used in attack potions.", true, 3, ItemType::Resource);
+        insert_item(&mut items, 111, "Tarromin", "A herb used in strength potions.", true, 10, ItemType::Resource);
+        insert_item(&mut items, 112, "Harralander", "A herb used in restore potions.", true, 20, ItemType::Resource);
+        insert_item(&mut items, 113, "Ranarr weed", "A herb used in defence potions.", true, 50, ItemType::Resource);
+        insert_item(&mut items, 115, "Vial of water", "A glass vial containing water.", true, 2, ItemType::Resource);
+        insert_item(&mut items, 116, "Guam potion (unf)", "I need another ingredient to finish this potion.", true, 3, ItemType::Resource);
+        insert_item(&mut items, 117, "Tarromin potion (unf)", "I need another ingredient to finish this potion.", true, 10, ItemType::Resource);
+        insert_item(&mut items, 118, "Harralander potion (unf)", "I need another ingredient to finish this potion.", true, 20, ItemType::Resource);
+        insert_item(&mut items, 119, "Ranarr potion (unf)", "I need another ingredient to finish this potion.", true, 50, ItemType::Resource);
+        insert_item(&mut items, 120, "Eye of newt", "It seems to be looking at me.", true, 3, ItemType::Resource);
+        insert_item(&mut items, 121, "Limpwurt root", "The root of a limpwurt plant.", true, 7, ItemType::Resource);
+        insert_item(&mut items, 122, "Red spiders' eggs", "Eeeeww... yuck!", true, 10, ItemType::Resource);
+        insert_item(&mut items, 123, "White berries", "Poisonous berries.", true, 10, ItemType::Resource);
+
+        // Potions, one item per number of doses left
+        for (first_id, name, value) in [(130, "Attack potion", 14), (134, "Strength potion", 24), (138, "Restore potion", 44), (142, "Defence potion", 88)] {
+            for (offset, doses) in (1..=4).rev().enumerate() {
+                insert_item(&mut items, first_id + offset as u64, &format!("{}({})", name, doses), "A potion in a glass vial.", false, value * doses / 4, ItemType::Consumable);
+            }
+        }
+        insert_item(&mut items, 146, "Vial", "An empty glass vial.", true, 1, ItemType::Resource);
+
+        // Market goods
+        insert_item(&mut items, 150, "Silk", "It's a sheet of silk.", true, 30, ItemType::Resource);
+
+        // Farming seeds, produce and supplies
+        insert_item(&mut items, 160, "Potato seed", "A potato seed - plant in an allotment.", true, 1, ItemType::Resource);
+        insert_item(&mut items, 161, "Onion seed", "An onion seed - plant in an allotment.", true, 2, ItemType::Resource);
+        insert_item(&mut items, 162, "Cabbage seed", "A cabbage seed - plant in an allotment.", true, 3, ItemType::Resource);
+        insert_item(&mut items, 163, "Guam seed", "A guam seed - plant in a herb patch.", true, 5, ItemType::Resource);
+        insert_item(&mut items, 164, "Ranarr seed", "A ranarr seed - plant in a herb patch.", true, 50, ItemType::Resource);
+        insert_item(&mut items, 165, "Potato", "A suspiciously vegetable-shaped potato.", true, 3, ItemType::Resource);
+        insert_item(&mut items, 166, "Onion", "A strong smelling onion.", true, 3, ItemType::Resource);
+        insert_item(&mut items, 167, "Cabbage", "Yuck, I don't like cabbage.", true, 1, ItemType::Resource);
+        insert_item(&mut items, 168, "Compost", "Good for plants, helps them grow.", true, 4, ItemType::Resource);
+        insert_item(&mut items, 169, "Supercompost", "Super-good for the smallest or largest of plants.", true, 35, ItemType::Resource);
+        insert_item(&mut items, 170, "Plant cure", "Use this to cure diseased crops.", true, 25, ItemType::Resource);
+
+        // Runecrafting essence, elemental runes and the catalytic runes spells also need
+        insert_item(&mut items, 175, "Rune essence", "An uncharged rune stone.", true, 4, ItemType::Resource);
+        insert_item(&mut items, 180, "Air rune", "One of the 4 basic elemental runes.", true, 4, ItemType::Consumable);
+        insert_item(&mut items, 181, "Water rune", "One of the 4 basic elemental runes.", true, 4, ItemType::Consumable);
+        insert_item(&mut items, 182, "Earth rune", "One of the 4 basic elemental runes.", true, 4, ItemType::Consumable);
+        insert_item(&mut items, 183, "Fire rune", "One of the 4 basic elemental runes.", true, 4, ItemType::Consumable);
+        insert_item(&mut items, 184, "Mind rune", "Used for basic level missile spells.", true, 3, ItemType::Consumable);
+        insert_item(&mut items, 185, "Chaos rune", "Used for medium level missile spells.", true, 90, ItemType::Consumable);
+        insert_item(&mut items, 186, "Death rune", "Used for high level missile spells.", true, 180, ItemType::Consumable);
+        insert_item(&mut items, 187, "Law rune", "Used for teleport spells.", true, 240, ItemType::Consumable);
+        insert_item(&mut items, 188, "Nature rune", "Used for alchemy spells.", true, 180, ItemType::Consumable);
+
+        // Bones
+        insert_item(&mut items, 190, "Bones", "Bones are for burying!", false, 1, ItemType::Consumable);
+        insert_item(&mut items, 191, "Big bones", "Ew, it's a pile of bones.", false, 10, ItemType::Consumable);
+        insert_item(&mut items, 192, "Dragon bones", "These would feed a dogfish for months!", false, 250, ItemType::Consumable);
+
+        let mut icons = HashMap::new();
+        let icon_groups: [(&[u64], &'static str); 5] = [
+            (&[1, 10, 11, 12], "🪵"),
+            (&[2, 175], "🪨"),
+            (&[3, 13, 14, 15, 16], "💎"),
+            (&[50, 51, 52, 53], "🧱"),
+            (&[180, 181, 182, 183, 184, 185, 186, 187, 188], "🌀"),
+        ];
+        for (item_ids, icon) in icon_groups {
+            for &item_id in item_ids {
+                icons.insert(item_id, icon);
+            }
+        }
+
+        Self { items, icons }
     }
 }
 
@@ -101,6 +289,7 @@ pub enum ItemType {
     Consumable,
     Food,
     Quest,
+    Tool,
 }
 
 // System to handle inventory updates
@@ -170,12 +359,22 @@ fn update_inventory_ui(
     }
 }
 
-// Helper function to get item ID from resource type
-pub fn get_item_id_for_resource(resource_type: &ResourceNodeType) -> u64 {
-    match resource_type {
-        ResourceNodeType::Tree => 1,       // Logs
-        ResourceNodeType::Rock => 2,       // Stone
-        ResourceNodeType::OreDeposit => 3, // Ore
-        ResourceNodeType::FishingSpot => 4, // Fish
-    }
+// Helper to add an item definition to the database
+fn insert_item(
+    items: &mut HashMap<u64, ItemDefinition>,
+    id: u64,
+    name: &str,
+    description: &str,
+    stackable: bool,
+    value: u32,
+    item_type: ItemType,
+) {
+    items.insert(id, ItemDefinition {
+        id,
+        name: name.to_string(),
+        description: description.to_string(),
+        stackable,
+        value,
+        item_type,
+    });
 }
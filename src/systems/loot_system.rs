@@ -0,0 +1,532 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::shared::entities::NPC;
+use crate::client::input::Player;
+use crate::systems::combat_system::EnemyKilledEvent;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, ItemDatabase, ItemType};
+use crate::systems::skills_system::spawn_floating_text;
+
+pub struct LootPlugin;
+
+impl Plugin for LootPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LootDatabase>()
+           .init_resource::<LootSettings>()
+           .add_systems(Update, (
+               drop_loot,
+               update_ground_items,
+               pick_up_ground_items,
+           ));
+    }
+}
+
+// Ground item settings
+#[derive(Resource)]
+pub struct LootSettings {
+    // Seconds before a drop can be seen and picked up by everyone, not just the killer
+    pub public_delay: f32,
+    // Seconds before an item left on the ground disappears
+    pub despawn_time: f32,
+}
+
+impl Default for LootSettings {
+    fn default() -> Self {
+        Self {
+            public_delay: 60.0,
+            despawn_time: 180.0,
+        }
+    }
+}
+
+// An item and how many of it are dropped
+#[derive(Clone, Debug)]
+pub struct LootDrop {
+    pub item_id: u64,
+    pub min_quantity: u32,
+    pub max_quantity: u32,
+}
+
+// An entry of a weighted table; no drop means the roll gives nothing
+#[derive(Clone, Debug)]
+pub struct WeightedDrop {
+    pub weight: u32,
+    pub drop: Option<LootDrop>,
+}
+
+// A drop rolled on its own, with a 1 in `chance` chance
+#[derive(Clone, Debug)]
+pub struct TertiaryDrop {
+    pub chance: u32,
+    pub drop: LootDrop,
+}
+
+// What an NPC type drops when it dies
+#[derive(Clone, Debug, Default)]
+pub struct LootTable {
+    // Dropped on every kill
+    pub always: Vec<LootDrop>,
+    // One entry is picked on every kill, by weight
+    pub common: Vec<WeightedDrop>,
+    // Replaces the common roll 1 in `rare_chance` kills
+    pub rare: Vec<WeightedDrop>,
+    pub rare_chance: u32,
+    // Rolled separately, on top of everything else
+    pub tertiary: Vec<TertiaryDrop>,
+}
+
+impl LootTable {
+    // Roll a kill's drops, returning (item ID, quantity, rare)
+    pub fn roll(&self, rng: &mut impl Rng) -> Vec<(u64, u32, bool)> {
+        let mut drops: Vec<(&LootDrop, bool)> = self.always.iter().map(|drop| (drop, false)).collect();
+
+        let rare_roll = !self.rare.is_empty() && self.rare_chance > 0 && rng.gen_range(0..self.rare_chance) == 0;
+        let (table, rare) = if rare_roll { (&self.rare, true) } else { (&self.common, false) };
+        if let Some(drop) = pick_weighted(table, rng) {
+            drops.push((drop, rare));
+        }
+
+        for tertiary in &self.tertiary {
+            if tertiary.chance > 0 && rng.gen_range(0..tertiary.chance) == 0 {
+                drops.push((&tertiary.drop, true));
+            }
+        }
+
+        drops.into_iter()
+            .map(|(drop, rare)| (drop.item_id, rng.gen_range(drop.min_quantity..=drop.max_quantity), rare))
+            .collect()
+    }
+}
+
+fn pick_weighted<'a>(table: &'a [WeightedDrop], rng: &mut impl Rng) -> Option<&'a LootDrop> {
+    let total: u32 = table.iter().map(|entry| entry.weight).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut roll = rng.gen_range(0..total);
+    for entry in table {
+        if roll < entry.weight {
+            return entry.drop.as_ref();
+        }
+        roll -= entry.weight;
+    }
+    None
+}
+
+// Resource for loot tables, keyed by NPC name
+#[derive(Resource)]
+pub struct LootDatabase {
+    pub tables: HashMap<String, LootTable>,
+}
+
+impl Default for LootDatabase {
+    fn default() -> Self {
+        let mut tables = HashMap::new();
+
+        // Uncut gems, the rare drop shared by most monsters
+        let gems = [weighted(32, 84, 1, 1), weighted(16, 85, 1, 1), weighted(8, 86, 1, 1)];
+
+        // Villagers
+        insert_table(&mut tables, "Man", &[loot(190, 1, 1)], &[
+            nothing(30),
+            weighted(5, 184, 2, 6),
+            weighted(8, 180, 4, 10),
+            weighted(6, 182, 2, 6),
+            weighted(6, 160, 1, 3),
+            weighted(3, 60, 1, 1),
+        ], 128, &gems, &[]);
+        insert_table(&mut tables, "Farmer", &[loot(190, 1, 1)], &[
+            nothing(20),
+            weighted(8, 160, 1, 4),
+            weighted(6, 161, 1, 3),
+            weighted(6, 162, 1, 3),
+            weighted(4, 168, 1, 1),
+        ], 128, &gems, &[tertiary(100, 163, 1, 1)]);
+        insert_table(&mut tables, "Guard", &[loot(190, 1, 1)], &[
+            nothing(15),
+            weighted(6, 63, 1, 1),
+            weighted(8, 180, 6, 12),
+            weighted(6, 183, 2, 6),
+            weighted(5, 14, 1, 2),
+        ], 64, &gems, &[]);
+
+        // Farm animals and goblins
+        insert_table(&mut tables, "Chicken", &[loot(190, 1, 1)], &[], 0, &[], &[]);
+        insert_table(&mut tables, "Cow", &[loot(190, 1, 1)], &[
+            nothing(1),
+            weighted(1, 83, 1, 1),
+        ], 0, &[], &[]);
+        insert_table(&mut tables, "Goblin", &[loot(190, 1, 1)], &[
+            nothing(20),
+            weighted(5, 184, 4, 10),
+            weighted(5, 60, 1, 1),
+            weighted(3, 61, 1, 1),
+            weighted(6, 180, 3, 8),
+            weighted(4, 181, 3, 6),
+            weighted(4, 182, 3, 6),
+            weighted(3, 110, 1, 1),
+        ], 128, &gems, &[]);
+
+        // Giants
+        insert_table(&mut tables, "Hill Giant", &[loot(191, 1, 1)], &[
+            nothing(10),
+            weighted(3, 187, 1, 2),
+            weighted(5, 64, 1, 1),
+            weighted(4, 66, 1, 1),
+            weighted(6, 183, 5, 15),
+            weighted(6, 15, 1, 3),
+            weighted(4, 121, 1, 2),
+            weighted(3, 111, 1, 1),
+        ], 64, &gems, &[tertiary(100, 164, 1, 1)]);
+        insert_table(&mut tables, "Moss Giant", &[loot(191, 1, 1)], &[
+            nothing(10),
+            weighted(4, 188, 2, 5),
+            weighted(5, 67, 1, 1),
+            weighted(3, 69, 1, 1),
+            weighted(6, 181, 10, 20),
+            weighted(6, 15, 2, 4),
+            weighted(4, 10, 3, 6),
+            weighted(3, 112, 1, 1),
+        ], 32, &gems, &[tertiary(80, 164, 1, 1)]);
+        insert_table(&mut tables, "Fire Giant", &[loot(191, 1, 1)], &[
+            nothing(8),
+            weighted(4, 186, 2, 5),
+            weighted(4, 68, 1, 1),
+            weighted(4, 70, 1, 1),
+            weighted(6, 183, 20, 40),
+            weighted(6, 15, 3, 5),
+            weighted(4, 12, 2, 5),
+            weighted(3, 113, 1, 1),
+        ], 32, &gems, &[tertiary(60, 164, 1, 2)]);
+
+        // Slayer monsters
+        insert_table(&mut tables, "Crawling Hand", &[loot(190, 1, 1)], &[
+            nothing(20),
+            weighted(6, 180, 3, 6),
+            weighted(4, 110, 1, 1),
+        ], 128, &gems, &[tertiary(50, 100, 1, 1)]);
+        insert_table(&mut tables, "Cave Crawler", &[loot(190, 1, 1)], &[
+            nothing(15),
+            weighted(6, 120, 1, 3),
+            weighted(5, 110, 1, 1),
+            weighted(4, 111, 1, 1),
+            weighted(3, 122, 1, 2),
+        ], 128, &gems, &[tertiary(100, 163, 1, 1)]);
+        insert_table(&mut tables, "Banshee", &[loot(190, 1, 1)], &[
+            nothing(15),
+            weighted(6, 181, 5, 10),
+            weighted(5, 111, 1, 1),
+            weighted(4, 112, 1, 1),
+        ], 96, &gems, &[tertiary(100, 101, 1, 1)]);
+        insert_table(&mut tables, "Rockslug", &[loot(190, 1, 1)], &[
+            nothing(12),
+            weighted(6, 14, 1, 3),
+            weighted(6, 15, 1, 2),
+            weighted(4, 182, 5, 12),
+        ], 96, &gems, &[]);
+        insert_table(&mut tables, "Pyrefiend", &[loot(190, 1, 1)], &[
+            nothing(12),
+            weighted(4, 185, 3, 8),
+            weighted(8, 183, 10, 20),
+            weighted(4, 64, 1, 1),
+            weighted(4, 112, 1, 1),
+        ], 64, &gems, &[]);
+        insert_table(&mut tables, "Gargoyle", &[loot(192, 1, 1)], &[
+            nothing(8),
+            weighted(6, 53, 1, 2),
+            weighted(6, 16, 2, 4),
+            weighted(4, 52, 2, 3),
+            weighted(5, 182, 20, 40),
+        ], 32, &gems, &[tertiary(60, 164, 1, 2)]);
+        insert_table(&mut tables, "Nechryael", &[loot(192, 1, 1)], &[
+            nothing(8),
+            weighted(5, 186, 5, 10),
+            weighted(6, 180, 30, 50),
+            weighted(6, 183, 20, 40),
+            weighted(4, 70, 1, 1),
+            weighted(4, 113, 1, 2),
+        ], 32, &gems, &[tertiary(60, 164, 1, 2)]);
+        insert_table(&mut tables, "Black Demon", &[loot(192, 1, 1)], &[
+            nothing(6),
+            weighted(4, 187, 3, 6),
+            weighted(5, 71, 1, 1),
+            weighted(5, 53, 2, 3),
+            weighted(6, 183, 30, 60),
+            weighted(4, 113, 1, 3),
+        ], 24, &gems, &[tertiary(40, 164, 1, 3)]);
+        insert_table(&mut tables, "Abyssal Demon", &[loot(192, 1, 1)], &[
+            nothing(6),
+            weighted(5, 188, 5, 12),
+            weighted(5, 70, 1, 1),
+            weighted(6, 181, 30, 60),
+            weighted(6, 182, 30, 60),
+            weighted(4, 113, 1, 3),
+        ], 24, &gems, &[tertiary(40, 164, 1, 3)]);
+
+        Self { tables }
+    }
+}
+
+// Helpers to define loot tables
+fn insert_table(
+    tables: &mut HashMap<String, LootTable>,
+    npc: &str,
+    always: &[LootDrop],
+    common: &[WeightedDrop],
+    rare_chance: u32,
+    rare: &[WeightedDrop],
+    tertiary: &[TertiaryDrop],
+) {
+    tables.insert(npc.to_string(), LootTable {
+        always: always.to_vec(),
+        common: common.to_vec(),
+        rare: rare.to_vec(),
+        rare_chance,
+        tertiary: tertiary.to_vec(),
+    });
+}
+
+fn loot(item_id: u64, min_quantity: u32, max_quantity: u32) -> LootDrop {
+    LootDrop {
+        item_id,
+        min_quantity,
+        max_quantity,
+    }
+}
+
+fn weighted(weight: u32, item_id: u64, min_quantity: u32, max_quantity: u32) -> WeightedDrop {
+    WeightedDrop {
+        weight,
+        drop: Some(loot(item_id, min_quantity, max_quantity)),
+    }
+}
+
+fn nothing(weight: u32) -> WeightedDrop {
+    WeightedDrop {
+        weight,
+        drop: None,
+    }
+}
+
+fn tertiary(chance: u32, item_id: u64, min_quantity: u32, max_quantity: u32) -> TertiaryDrop {
+    TertiaryDrop {
+        chance,
+        drop: loot(item_id, min_quantity, max_quantity),
+    }
+}
+
+// An item lying on the ground
+#[derive(Component)]
+pub struct GroundItem {
+    pub item_id: u64,
+    pub quantity: u32,
+    // Only this player can see and pick up the item until it goes public
+    pub owner: Option<Entity>,
+    pub public_timer: Timer,
+    pub despawn_timer: Timer,
+}
+
+impl GroundItem {
+    pub fn visible_to(&self, player: Entity) -> bool {
+        self.owner.map_or(true, |owner| owner == player)
+    }
+}
+
+// Colour of the marker for an item on the ground
+fn ground_item_color(item_type: &ItemType) -> Color {
+    match item_type {
+        ItemType::Resource => Color::rgb(0.6, 0.5, 0.35),
+        ItemType::Equipment => Color::rgb(0.7, 0.7, 0.75),
+        ItemType::Consumable => Color::rgb(0.9, 0.9, 0.8),
+        ItemType::Food => Color::rgb(0.85, 0.5, 0.3),
+        ItemType::Quest => Color::rgb(0.9, 0.8, 0.2),
+        ItemType::Tool => Color::rgb(0.5, 0.4, 0.3),
+    }
+}
+
+// Roll a killed enemy's loot table and leave the drops on the ground where it died
+#[allow(clippy::too_many_arguments)]
+fn drop_loot(
+    mut commands: Commands,
+    mut events: EventReader<EnemyKilledEvent>,
+    database: Res<LootDatabase>,
+    item_database: Res<ItemDatabase>,
+    settings: Res<LootSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    player_query: Query<Entity, With<Player>>,
+    enemy_query: Query<(&Transform, &NPC)>,
+) {
+    let mut rng = rand::thread_rng();
+
+    for event in events.read() {
+        let Ok((enemy_transform, npc)) = enemy_query.get(event.enemy) else {
+            continue;
+        };
+        let Some(table) = database.tables.get(&npc.name) else {
+            continue;
+        };
+        let owner = if event.by_player { player_query.get_single().ok() } else { None };
+        let position = Vec3::new(enemy_transform.translation.x, 0.15, enemy_transform.translation.z);
+
+        for (item_id, quantity, rare) in table.roll(&mut rng) {
+            let Some(item) = item_database.items.get(&item_id) else {
+                println!("Warning: {} loot table uses unknown item: {}", npc.name, item_id);
+                continue;
+            };
+
+            // Scatter drops a little so they don't all sit on top of each other
+            let offset = Vec3::new(rng.gen_range(-0.5..0.5), 0.0, rng.gen_range(-0.5..0.5));
+            commands.spawn((
+                PbrBundle {
+                    mesh: meshes.add(Mesh::from(shape::Box::new(0.3, 0.3, 0.3))),
+                    material: materials.add(ground_item_color(&item.item_type).into()),
+                    transform: Transform::from_translation(position + offset),
+                    ..default()
+                },
+                GroundItem {
+                    item_id,
+                    quantity,
+                    owner,
+                    public_timer: Timer::from_seconds(settings.public_delay, TimerMode::Once),
+                    despawn_timer: Timer::from_seconds(settings.despawn_time, TimerMode::Once),
+                },
+            ));
+
+            if rare && owner.is_some() {
+                let message = format!("Valuable drop: {} x {}", item.name, quantity);
+                println!("{}", message);
+                spawn_floating_text(&mut commands, position + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(1.0, 0.8, 0.2));
+            }
+        }
+    }
+}
+
+// Make drops public once their delay is up, hide ones the player can't see yet, and remove old ones
+fn update_ground_items(
+    mut commands: Commands,
+    player_query: Query<Entity, With<Player>>,
+    mut item_query: Query<(Entity, &mut GroundItem, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    let player = player_query.get_single().ok();
+
+    for (entity, mut ground_item, mut visibility) in item_query.iter_mut() {
+        ground_item.despawn_timer.tick(time.delta());
+        if ground_item.despawn_timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        if ground_item.owner.is_some() {
+            ground_item.public_timer.tick(time.delta());
+            if ground_item.public_timer.finished() {
+                ground_item.owner = None;
+            }
+        }
+
+        let visible = player.map_or(ground_item.owner.is_none(), |player| ground_item.visible_to(player));
+        *visibility = if visible { Visibility::Visible } else { Visibility::Hidden };
+    }
+}
+
+// Press F next to a dropped item to pick it up
+fn pick_up_ground_items(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    item_database: Res<ItemDatabase>,
+    player_query: Query<(Entity, &Transform, &Inventory), With<Player>>,
+    item_query: Query<&GroundItem>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Some(entity) = interact_events.read().last().map(|event| event.target) else {
+        return;
+    };
+    let Ok(ground_item) = item_query.get(entity) else {
+        return;
+    };
+    let Ok((player_entity, player_transform, inventory)) = player_query.get_single() else {
+        return;
+    };
+    if !ground_item.visible_to(player_entity) {
+        return;
+    }
+
+    if !inventory.has_room_for(ground_item.item_id) {
+        println!("You don't have enough inventory space to pick that up.");
+        return;
+    }
+
+    inventory_events.send(InventoryUpdateEvent {
+        item_id: ground_item.item_id,
+        quantity: ground_item.quantity as i32,
+    });
+    commands.entity(entity).despawn_recursive();
+
+    let name = item_database.items.get(&ground_item.item_id).map_or("item", |item| item.name.as_str());
+    spawn_floating_text(
+        &mut commands,
+        player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+        format!("You pick up: {} x {}", name, ground_item.quantity),
+        Color::rgb(0.9, 0.9, 0.8),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn always_drops_appear_on_every_kill() {
+        let table = LootTable {
+            always: vec![loot(190, 1, 1)],
+            common: vec![nothing(10), weighted(1, 180, 2, 6)],
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(47);
+
+        for _ in 0..200 {
+            let drops = table.roll(&mut rng);
+            assert!(drops.contains(&(190, 1, false)), "bones missing from {:?}", drops);
+        }
+    }
+
+    #[test]
+    fn zero_weight_entries_are_never_picked() {
+        let table = [weighted(0, 1, 1, 1), weighted(3, 2, 1, 1), weighted(0, 3, 1, 1)];
+        let mut rng = StdRng::seed_from_u64(47);
+
+        for _ in 0..500 {
+            assert_eq!(pick_weighted(&table, &mut rng).map(|drop| drop.item_id), Some(2));
+        }
+    }
+
+    #[test]
+    fn empty_or_weightless_tables_drop_nothing() {
+        let mut rng = StdRng::seed_from_u64(47);
+
+        assert!(pick_weighted(&[], &mut rng).is_none());
+        assert!(pick_weighted(&[weighted(0, 1, 1, 1)], &mut rng).is_none());
+    }
+
+    #[test]
+    fn quantities_stay_within_their_range() {
+        let table = LootTable {
+            common: vec![weighted(1, 180, 4, 10)],
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(47);
+
+        for _ in 0..200 {
+            for (item_id, quantity, rare) in table.roll(&mut rng) {
+                assert_eq!(item_id, 180);
+                assert!((4..=10).contains(&quantity));
+                assert!(!rare);
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use crate::shared::components::Skills;
+use crate::client::input::Player;
+use crate::client::physics::Velocity;
+use crate::systems::combat_system::{CombatState, CombatStyle};
+use crate::systems::experience::level_for_experience;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, ItemDatabase};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Item IDs of the runes spells are cast with
+pub const AIR_RUNE: u64 = 180;
+pub const WATER_RUNE: u64 = 181;
+pub const EARTH_RUNE: u64 = 182;
+pub const FIRE_RUNE: u64 = 183;
+pub const MIND_RUNE: u64 = 184;
+pub const CHAOS_RUNE: u64 = 185;
+pub const DEATH_RUNE: u64 = 186;
+pub const LAW_RUNE: u64 = 187;
+pub const NATURE_RUNE: u64 = 188;
+
+pub struct MagicPlugin;
+
+impl Plugin for MagicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpellDatabase>()
+           .add_event::<SelectAutocastEvent>()
+           .add_event::<CastSpellEvent>()
+           .add_systems(Update, (
+               select_autocast,
+               cast_utility_spells,
+           ));
+    }
+}
+
+// What casting a spell does
+#[derive(Clone, Debug)]
+pub enum SpellEffect {
+    // Launch an orb at the combat target
+    Combat { max_hit: u32, color: Color },
+    // Move the caster somewhere else in the world
+    Teleport { destination: Vec3 },
+    // Turn an item into gold, worth a fraction of its value
+    Alchemy { value_multiplier: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct SpellDefinition {
+    pub name: String,
+    pub level_requirement: u32,
+    // Runes used up by each cast (item ID, amount)
+    pub runes: Vec<(u64, u32)>,
+    // Magic XP for casting, on top of any XP for the damage done
+    pub experience: u32,
+    pub effect: SpellEffect,
+}
+
+impl SpellDefinition {
+    pub fn is_combat(&self) -> bool {
+        matches!(self.effect, SpellEffect::Combat { .. })
+    }
+
+    pub fn has_runes(&self, inventory: &Inventory) -> bool {
+        self.runes.iter().all(|(item_id, amount)| inventory.items.get(item_id).copied().unwrap_or(0) >= *amount)
+    }
+
+    // Use up the runes for one cast
+    pub fn consume_runes(&self, inventory_events: &mut EventWriter<InventoryUpdateEvent>) {
+        for &(item_id, amount) in &self.runes {
+            inventory_events.send(InventoryUpdateEvent {
+                item_id,
+                quantity: -(amount as i32),
+            });
+        }
+    }
+}
+
+// Resource for the spellbook (a spell's ID is its index)
+#[derive(Resource)]
+pub struct SpellDatabase {
+    pub spells: Vec<SpellDefinition>,
+}
+
+impl Default for SpellDatabase {
+    fn default() -> Self {
+        let wind = Color::rgb(0.85, 0.95, 0.9);
+        let water = Color::rgb(0.2, 0.45, 1.0);
+        let earth = Color::rgb(0.45, 0.7, 0.2);
+        let fire = Color::rgb(1.0, 0.4, 0.1);
+
+        let spells = vec![
+            // Strike spells
+            combat_spell("Wind Strike", 1, &[(AIR_RUNE, 1), (MIND_RUNE, 1)], 6, 2, wind),
+            combat_spell("Water Strike", 5, &[(WATER_RUNE, 1), (AIR_RUNE, 1), (MIND_RUNE, 1)], 8, 4, water),
+            combat_spell("Earth Strike", 9, &[(EARTH_RUNE, 2), (AIR_RUNE, 1), (MIND_RUNE, 1)], 10, 6, earth),
+            combat_spell("Fire Strike", 13, &[(FIRE_RUNE, 3), (AIR_RUNE, 2), (MIND_RUNE, 1)], 12, 8, fire),
+
+            // Bolt spells
+            combat_spell("Wind Bolt", 17, &[(AIR_RUNE, 2), (CHAOS_RUNE, 1)], 14, 9, wind),
+            combat_spell("Water Bolt", 23, &[(WATER_RUNE, 2), (AIR_RUNE, 2), (CHAOS_RUNE, 1)], 17, 10, water),
+            combat_spell("Earth Bolt", 29, &[(EARTH_RUNE, 3), (AIR_RUNE, 2), (CHAOS_RUNE, 1)], 20, 11, earth),
+            combat_spell("Fire Bolt", 35, &[(FIRE_RUNE, 4), (AIR_RUNE, 3), (CHAOS_RUNE, 1)], 23, 12, fire),
+
+            // Blast spells
+            combat_spell("Wind Blast", 41, &[(AIR_RUNE, 3), (DEATH_RUNE, 1)], 26, 13, wind),
+            combat_spell("Water Blast", 47, &[(WATER_RUNE, 3), (AIR_RUNE, 3), (DEATH_RUNE, 1)], 29, 14, water),
+            combat_spell("Earth Blast", 53, &[(EARTH_RUNE, 4), (AIR_RUNE, 3), (DEATH_RUNE, 1)], 32, 15, earth),
+            combat_spell("Fire Blast", 59, &[(FIRE_RUNE, 5), (AIR_RUNE, 4), (DEATH_RUNE, 1)], 35, 16, fire),
+
+            // Teleports
+            spell("Village Teleport", 25, &[(FIRE_RUNE, 1), (AIR_RUNE, 3), (LAW_RUNE, 1)], 35, SpellEffect::Teleport { destination: Vec3::new(0.0, 1.0, 4.0) }),
+            spell("Farm Teleport", 31, &[(EARTH_RUNE, 1), (AIR_RUNE, 3), (LAW_RUNE, 1)], 41, SpellEffect::Teleport { destination: Vec3::new(22.0, 1.0, 9.0) }),
+            spell("Plateau Teleport", 37, &[(WATER_RUNE, 1), (AIR_RUNE, 3), (LAW_RUNE, 1)], 48, SpellEffect::Teleport { destination: Vec3::new(32.0, 1.0, 27.0) }),
+
+            // Alchemy
+            spell("Low Level Alchemy", 21, &[(FIRE_RUNE, 3), (NATURE_RUNE, 1)], 31, SpellEffect::Alchemy { value_multiplier: 0.4 }),
+            spell("High Level Alchemy", 55, &[(FIRE_RUNE, 5), (NATURE_RUNE, 1)], 65, SpellEffect::Alchemy { value_multiplier: 0.6 }),
+        ];
+
+        Self { spells }
+    }
+}
+
+// Helpers to define spells
+fn spell(name: &str, level_requirement: u32, runes: &[(u64, u32)], experience: u32, effect: SpellEffect) -> SpellDefinition {
+    SpellDefinition {
+        name: name.to_string(),
+        level_requirement,
+        runes: runes.to_vec(),
+        experience,
+        effect,
+    }
+}
+
+fn combat_spell(name: &str, level_requirement: u32, runes: &[(u64, u32)], experience: u32, max_hit: u32, color: Color) -> SpellDefinition {
+    spell(name, level_requirement, runes, experience, SpellEffect::Combat { max_hit, color })
+}
+
+// The player's chosen autocast spell, cast by every Magic attack
+#[derive(Component, Default)]
+pub struct Spellbook {
+    pub autocast: Option<usize>,
+}
+
+// Event for choosing (or clearing) the autocast spell
+#[derive(Event)]
+pub struct SelectAutocastEvent {
+    pub spell_id: Option<usize>,
+}
+
+// Event for casting a non-combat spell, with the item to cast it on for alchemy
+#[derive(Event)]
+pub struct CastSpellEvent {
+    pub spell_id: usize,
+    pub target_item_id: Option<u64>,
+}
+
+// Set the autocast spell and switch to Magic to fight with it
+fn select_autocast(
+    mut events: EventReader<SelectAutocastEvent>,
+    database: Res<SpellDatabase>,
+    mut player_query: Query<(&Skills, &mut Spellbook, &mut CombatState), With<Player>>,
+) {
+    let Ok((skills, mut spellbook, mut combat_state)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let Some(spell_id) = event.spell_id else {
+            spellbook.autocast = None;
+            continue;
+        };
+        let Some(spell) = database.spells.get(spell_id).filter(|spell| spell.is_combat()) else {
+            continue;
+        };
+        if level_for_experience(skills.magic) < spell.level_requirement {
+            println!("You need a magic level of {} to cast {}.", spell.level_requirement, spell.name);
+            continue;
+        }
+
+        spellbook.autocast = Some(spell_id);
+        if combat_state.current_style != CombatStyle::Magic {
+            combat_state.set_style(CombatStyle::Magic);
+        }
+        println!("Autocasting {}", spell.name);
+    }
+}
+
+// Cast teleports and alchemy from the spellbook
+#[allow(clippy::too_many_arguments)]
+fn cast_utility_spells(
+    mut commands: Commands,
+    mut events: EventReader<CastSpellEvent>,
+    database: Res<SpellDatabase>,
+    item_database: Res<ItemDatabase>,
+    mut player_query: Query<(&mut Transform, &mut Velocity, &mut CombatState, &mut Inventory, &Skills), With<Player>>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+) {
+    let Ok((mut transform, mut velocity, mut combat_state, mut inventory, skills)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let Some(spell) = database.spells.get(event.spell_id) else {
+            continue;
+        };
+        if level_for_experience(skills.magic) < spell.level_requirement {
+            println!("You need a magic level of {} to cast {}.", spell.level_requirement, spell.name);
+            continue;
+        }
+        if !spell.has_runes(&inventory) {
+            println!("You do not have enough runes to cast {}.", spell.name);
+            continue;
+        }
+
+        let message = match spell.effect {
+            SpellEffect::Combat { .. } => continue,
+            SpellEffect::Teleport { destination } => {
+                transform.translation = destination;
+                velocity.linear = Vec3::ZERO;
+                combat_state.target = None;
+                format!("You cast {}.", spell.name)
+            },
+            SpellEffect::Alchemy { value_multiplier } => {
+                let Some(item_id) = event.target_item_id.filter(|item_id| inventory.items.contains_key(item_id)) else {
+                    continue;
+                };
+                let Some(item) = item_database.items.get(&item_id) else {
+                    continue;
+                };
+                if spell.runes.iter().any(|(rune_id, _)| *rune_id == item_id) {
+                    println!("You can't use {} on the runes it's cast with.", spell.name);
+                    continue;
+                }
+
+                let gold = (item.value as f32 * value_multiplier) as u32;
+                inventory.gold += gold;
+                inventory_events.send(InventoryUpdateEvent {
+                    item_id,
+                    quantity: -1,
+                });
+                format!("Your {} turns into {} gold.", item.name, gold)
+            },
+        };
+
+        spell.consume_runes(&mut inventory_events);
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "magic".to_string(),
+            experience: spell.experience,
+        });
+        println!("{}", message);
+        spawn_floating_text(&mut commands, transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.6, 0.7, 1.0));
+    }
+}
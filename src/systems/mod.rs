@@ -6,11 +6,51 @@ pub mod quests;
 pub mod skills_system;
 pub mod combat_system;
 pub mod inventory_system;
+pub mod experience;
+pub mod resources;
+pub mod cooking_system;
+pub mod smithing_system;
+pub mod production_system;
+pub mod herblore_system;
+pub mod agility_system;
+pub mod thieving_system;
+pub mod farming_system;
+pub mod runecrafting_system;
+pub mod prayer_system;
+pub mod slayer_system;
+pub mod equipment_system;
+pub mod spawning_system;
+pub mod death_system;
+pub mod loot_system;
+pub mod pathfinding;
+pub mod npc_ai_system;
+pub mod projectile_system;
+pub mod magic_system;
+pub mod interaction_system;
 
 use bevy::prelude::*;
 use skills_system::SkillsPlugin;
 use combat_system::CombatPlugin;
 use inventory_system::InventoryPlugin;
+use resources::ResourcesPlugin;
+use cooking_system::CookingPlugin;
+use smithing_system::SmithingPlugin;
+use production_system::ProductionPlugin;
+use herblore_system::HerblorePlugin;
+use agility_system::AgilityPlugin;
+use thieving_system::ThievingPlugin;
+use farming_system::FarmingPlugin;
+use runecrafting_system::RunecraftingPlugin;
+use prayer_system::PrayerPlugin;
+use slayer_system::SlayerPlugin;
+use equipment_system::EquipmentPlugin;
+use spawning_system::SpawningPlugin;
+use death_system::DeathPlugin;
+use loot_system::LootPlugin;
+use npc_ai_system::NpcAiPlugin;
+use projectile_system::ProjectilePlugin;
+use magic_system::MagicPlugin;
+use interaction_system::InteractionPlugin;
 
 pub struct GameSystemsPlugin;
 
@@ -18,7 +58,26 @@ impl Plugin for GameSystemsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(SkillsPlugin)
            .add_plugins(CombatPlugin)
+           .add_plugins(ProjectilePlugin)
+           .add_plugins(MagicPlugin)
+           .add_plugins(SpawningPlugin)
+           .add_plugins(NpcAiPlugin)
+           .add_plugins(DeathPlugin)
+           .add_plugins(LootPlugin)
            .add_plugins(InventoryPlugin)
+           .add_plugins(EquipmentPlugin)
+           .add_plugins(ResourcesPlugin)
+           .add_plugins(CookingPlugin)
+           .add_plugins(SmithingPlugin)
+           .add_plugins(ProductionPlugin)
+           .add_plugins(HerblorePlugin)
+           .add_plugins(AgilityPlugin)
+           .add_plugins(ThievingPlugin)
+           .add_plugins(FarmingPlugin)
+           .add_plugins(RunecraftingPlugin)
+           .add_plugins(PrayerPlugin)
+           .add_plugins(SlayerPlugin)
+           .add_plugins(InteractionPlugin)
            .add_systems(Startup, systems_setup);
     }
 }
@@ -0,0 +1,313 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::shared::components::{Health, Skills};
+use crate::client::input::Player;
+use crate::client::physics::{Collider, Velocity};
+use crate::systems::combat::combat_level;
+use crate::systems::combat_system::{CombatEvent, CombatSettings, CombatState, CombatStyle, DamageEvent, Enemy};
+use crate::systems::pathfinding::{Obstacle, PathSettings, find_path};
+use crate::systems::spawning_system::SpawnedNpc;
+
+pub struct NpcAiPlugin;
+
+impl Plugin for NpcAiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiSettings>()
+           .add_systems(Update, (
+               retaliate_when_attacked,
+               update_npc_behaviour,
+               move_npcs,
+           ).chain());
+    }
+}
+
+// NPC behaviour settings
+#[derive(Resource)]
+pub struct AiSettings {
+    pub walk_speed: f32,
+    pub chase_speed: f32,
+    pub flee_speed: f32,
+    // How far from home an NPC will chase before giving up and walking back
+    pub leash_range: f32,
+    // How far a fleeing NPC runs from its attacker before it feels safe
+    pub flee_distance: f32,
+    // Seconds an idle NPC waits before wandering somewhere else
+    pub min_idle_time: f32,
+    pub max_idle_time: f32,
+    // Seconds between recalculating a path to a moving target
+    pub repath_interval: f32,
+    pub path: PathSettings,
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self {
+            walk_speed: 1.5,
+            chase_speed: 3.5,
+            flee_speed: 3.0,
+            leash_range: 15.0,
+            flee_distance: 10.0,
+            min_idle_time: 2.0,
+            max_idle_time: 6.0,
+            repath_interval: 0.5,
+            path: PathSettings {
+                cell_size: 0.75,
+                clearance: 0.5,
+                max_nodes: 1500,
+            },
+        }
+    }
+}
+
+// What an NPC is currently doing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AiBehaviour {
+    // Standing still until the idle timer runs out
+    Idle,
+    // Walking to a random spot near home
+    Wander { destination: Vec3 },
+    // Going after a target and attacking it when in range
+    Chase { target: Entity },
+    // Walking home after being pulled too far away
+    Return,
+    // Running away from an attacker
+    Flee { from: Entity },
+}
+
+// Behaviour state of an NPC, and the path it's following
+#[derive(Component, Debug)]
+pub struct NpcAi {
+    pub behaviour: AiBehaviour,
+    // Fraction of health below which the NPC runs away (0 never flees)
+    pub flee_health: f32,
+    pub idle_timer: Timer,
+    pub path: Vec<Vec3>,
+    // Where the current path leads, and when to look for a new one
+    pub path_goal: Option<Vec3>,
+    pub repath_timer: Timer,
+}
+
+impl NpcAi {
+    pub fn new(flee_health: f32) -> Self {
+        Self {
+            behaviour: AiBehaviour::Idle,
+            flee_health,
+            idle_timer: Timer::from_seconds(rand::thread_rng().gen_range(0.0..4.0), TimerMode::Once),
+            path: Vec::new(),
+            path_goal: None,
+            repath_timer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+
+    fn set_behaviour(&mut self, behaviour: AiBehaviour) {
+        if self.behaviour != behaviour {
+            self.behaviour = behaviour;
+            self.path.clear();
+            self.path_goal = None;
+        }
+    }
+
+    fn start_idling(&mut self, settings: &AiSettings) {
+        let duration = rand::thread_rng().gen_range(settings.min_idle_time..settings.max_idle_time);
+        self.idle_timer = Timer::from_seconds(duration, TimerMode::Once);
+        self.set_behaviour(AiBehaviour::Idle);
+    }
+}
+
+// NPCs fight back against whoever hits them, even if they aren't aggressive
+fn retaliate_when_attacked(
+    mut events: EventReader<DamageEvent>,
+    player_query: Query<Entity, With<Player>>,
+    mut enemy_query: Query<(&Health, &mut CombatState), With<Enemy>>,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for event in events.read() {
+        if !event.is_player_source {
+            continue;
+        }
+        if let Ok((health, mut combat_state)) = enemy_query.get_mut(event.target) {
+            if health.current > 0 {
+                combat_state.target = Some(player_entity);
+            }
+        }
+    }
+}
+
+// Pick each NPC's behaviour and attack whatever it's chasing once it's in range
+#[allow(clippy::type_complexity)]
+fn update_npc_behaviour(
+    time: Res<Time>,
+    settings: Res<AiSettings>,
+    combat_settings: Res<CombatSettings>,
+    mut enemy_query: Query<(Entity, &Transform, &Enemy, &mut Health, &mut CombatState, &mut NpcAi, &SpawnedNpc)>,
+    player_query: Query<(Entity, &Transform, &Skills), With<Player>>,
+    // Whatever an NPC is chasing or fleeing from; NPCs don't fight each other
+    target_query: Query<&Transform, Without<Enemy>>,
+    mut combat_events: EventWriter<CombatEvent>,
+) {
+    let player = player_query.get_single().ok();
+    let mut rng = rand::thread_rng();
+
+    for (enemy_entity, enemy_transform, enemy, mut health, mut combat_state, mut ai, spawned) in enemy_query.iter_mut() {
+        // The dead don't fight back
+        if health.current == 0 {
+            continue;
+        }
+        combat_state.attack_timer.tick(time.delta());
+        ai.repath_timer.tick(time.delta());
+
+        let position = enemy_transform.translation;
+        let distance_from_home = position.xz().distance(spawned.home.xz());
+
+        // Look for a fight: a target set elsewhere (retaliation, guards), or a player wandering into aggression range
+        let noticed_player = player.and_then(|(player_entity, player_transform, skills)| {
+            let in_range = position.distance(player_transform.translation) < enemy.aggression_range;
+            let ignores_player = combat_level(skills) > enemy.level * combat_settings.aggression_level_multiplier;
+            (in_range && !ignores_player).then_some(player_entity)
+        });
+        let wants_to_fight = combat_state.target.or(noticed_player);
+
+        if let (AiBehaviour::Idle | AiBehaviour::Wander { .. }, Some(target)) = (ai.behaviour, wants_to_fight) {
+            combat_state.target = Some(target);
+            ai.set_behaviour(AiBehaviour::Chase { target });
+            continue;
+        }
+        let low_health = (health.current as f32) < health.maximum as f32 * ai.flee_health;
+
+        let behaviour = ai.behaviour;
+        match behaviour {
+            AiBehaviour::Idle => {
+                ai.idle_timer.tick(time.delta());
+                if ai.idle_timer.finished() && spawned.wander_radius > 0.0 {
+                    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                    let distance = rng.gen_range(0.0..spawned.wander_radius);
+                    let destination = spawned.home + Vec3::new(angle.cos() * distance, 0.0, angle.sin() * distance);
+                    ai.set_behaviour(AiBehaviour::Wander { destination: Vec3::new(destination.x, position.y, destination.z) });
+                }
+            },
+            AiBehaviour::Wander { destination } => {
+                if position.xz().distance(destination.xz()) < 0.3 {
+                    ai.start_idling(&settings);
+                }
+            },
+            AiBehaviour::Chase { target } => {
+                let target_position = target_query.get(target).ok().map(|target_transform| target_transform.translation);
+
+                if low_health {
+                    ai.set_behaviour(AiBehaviour::Flee { from: target });
+                } else if combat_state.target != Some(target) || target_position.is_none() || distance_from_home > settings.leash_range {
+                    // Lost the target, or pulled too far from home
+                    combat_state.target = None;
+                    ai.set_behaviour(AiBehaviour::Return);
+                } else if let Some(target_position) = target_position {
+                    if position.distance(target_position) < enemy.attack_range && combat_state.attack_timer.finished() {
+                        combat_state.attack_timer.reset();
+                        combat_events.send(CombatEvent {
+                            attacker: enemy_entity,
+                            target,
+                            style: CombatStyle::Melee, // Enemies use melee by default
+                            spell: None,
+                        });
+                    }
+                }
+            },
+            AiBehaviour::Flee { from } => {
+                let safe = target_query.get(from)
+                    .map_or(true, |from_transform| position.distance(from_transform.translation) > settings.flee_distance);
+                if safe || distance_from_home > settings.leash_range {
+                    combat_state.target = None;
+                    ai.set_behaviour(AiBehaviour::Return);
+                }
+            },
+            AiBehaviour::Return => {
+                // Back home, an NPC forgets the fight and recovers
+                if distance_from_home < 0.5 {
+                    health.current = health.maximum;
+                    ai.start_idling(&settings);
+                }
+            },
+        }
+    }
+}
+
+// Steer NPCs along a path towards wherever their behaviour wants them to be
+#[allow(clippy::type_complexity)]
+fn move_npcs(
+    settings: Res<AiSettings>,
+    mut enemy_query: Query<(&mut Transform, &mut Velocity, &Enemy, &Health, &mut NpcAi, &SpawnedNpc), Without<Player>>,
+    target_query: Query<&Transform, Without<Enemy>>,
+    obstacle_query: Query<(&Transform, &Collider), (Without<Enemy>, Without<Player>)>,
+) {
+    for (mut transform, mut velocity, enemy, health, mut ai, spawned) in enemy_query.iter_mut() {
+        if health.current == 0 {
+            velocity.linear = Vec3::ZERO;
+            continue;
+        }
+        let position = transform.translation;
+        let target_position = |entity: Entity| target_query.get(entity).ok().map(|target_transform| target_transform.translation);
+
+        // Where to go this frame, and how fast
+        let (goal, speed) = match ai.behaviour {
+            AiBehaviour::Idle => (None, 0.0),
+            AiBehaviour::Wander { destination } => (Some(destination), settings.walk_speed),
+            AiBehaviour::Chase { target } => {
+                // Stop once close enough to attack
+                let goal = target_position(target).filter(|target_position| position.distance(*target_position) >= enemy.attack_range * 0.8);
+                (goal, settings.chase_speed)
+            },
+            AiBehaviour::Return => (Some(spawned.home), settings.walk_speed),
+            AiBehaviour::Flee { from } => {
+                let goal = target_position(from).map(|from_position| {
+                    let away = Vec3::new(position.x - from_position.x, 0.0, position.z - from_position.z).normalize_or_zero();
+                    position + away * settings.flee_distance
+                });
+                (goal, settings.flee_speed)
+            },
+        };
+
+        let Some(goal) = goal.map(|goal| Vec3::new(goal.x, position.y, goal.z)) else {
+            ai.path.clear();
+            ai.path_goal = None;
+            velocity.linear = Vec3::ZERO;
+            continue;
+        };
+
+        // Recalculate the path when the goal moves or the current one runs out
+        let goal_moved = ai.path_goal.map_or(true, |path_goal| path_goal.distance(goal) > 1.0);
+        if ai.path.is_empty() || (goal_moved && ai.repath_timer.finished()) {
+            let search_radius = position.distance(goal) + 5.0;
+            let obstacles: Vec<Obstacle> = obstacle_query.iter()
+                .filter(|(obstacle_transform, _)| obstacle_transform.translation.xz().distance(position.xz()) < search_radius)
+                .map(|(obstacle_transform, collider)| Obstacle {
+                    position: obstacle_transform.translation,
+                    radius: collider.radius,
+                })
+                .collect();
+
+            // Head straight for the goal if it can't be reached around the obstacles
+            ai.path = find_path(position, goal, &obstacles, &settings.path).unwrap_or_else(|| vec![goal]);
+            ai.path_goal = Some(goal);
+            ai.repath_timer = Timer::from_seconds(settings.repath_interval, TimerMode::Once);
+        }
+
+        // Move on to the next waypoint once this one is reached
+        while ai.path.first().is_some_and(|waypoint| waypoint.xz().distance(position.xz()) < 0.2) {
+            ai.path.remove(0);
+        }
+        let Some(&waypoint) = ai.path.first() else {
+            velocity.linear = Vec3::ZERO;
+            continue;
+        };
+
+        let direction = Vec3::new(waypoint.x - position.x, 0.0, waypoint.z - position.z).normalize_or_zero();
+        velocity.linear = direction * speed;
+
+        // Face the way the NPC is walking (models look along their local X axis)
+        if direction != Vec3::ZERO {
+            transform.rotation = Quat::from_rotation_y((-direction.z).atan2(direction.x));
+        }
+    }
+}
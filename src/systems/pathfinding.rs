@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+// A circular obstacle on the ground plane (position and radius)
+#[derive(Clone, Copy, Debug)]
+pub struct Obstacle {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+// Settings for a path search
+#[derive(Clone, Copy, Debug)]
+pub struct PathSettings {
+    // Size of a grid cell
+    pub cell_size: f32,
+    // How far the walker has to keep from obstacles
+    pub clearance: f32,
+    // Give up after exploring this many cells
+    pub max_nodes: usize,
+}
+
+type Cell = (i32, i32);
+
+// Open set entry, ordered so the BinaryHeap pops the lowest estimated cost first
+#[derive(PartialEq)]
+struct OpenCell {
+    cell: Cell,
+    estimate: f32,
+}
+
+impl Eq for OpenCell {}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.partial_cmp(&self.estimate).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Horizontal distance from a point to a segment, ignoring height
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return point.distance(start);
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
+
+// Whether a straight walk between two points stays clear of every obstacle
+pub fn line_is_clear(start: Vec3, end: Vec3, obstacles: &[Obstacle], clearance: f32) -> bool {
+    let (start, end) = (start.xz(), end.xz());
+    obstacles.iter().all(|obstacle| distance_to_segment(obstacle.position.xz(), start, end) >= obstacle.radius + clearance)
+}
+
+// Find waypoints from start to goal that walk around obstacles, using A* on a grid.
+// Returns None if no path is found within the search limit.
+pub fn find_path(start: Vec3, goal: Vec3, obstacles: &[Obstacle], settings: &PathSettings) -> Option<Vec<Vec3>> {
+    if line_is_clear(start, goal, obstacles, settings.clearance) {
+        return Some(vec![goal]);
+    }
+
+    let to_cell = |position: Vec3| -> Cell {
+        ((position.x / settings.cell_size).round() as i32, (position.z / settings.cell_size).round() as i32)
+    };
+    let to_position = |cell: Cell| -> Vec3 {
+        Vec3::new(cell.0 as f32 * settings.cell_size, start.y, cell.1 as f32 * settings.cell_size)
+    };
+    let start_cell = to_cell(start);
+    let goal_cell = to_cell(goal);
+
+    // How far a cell is inside the nearest obstacle's clearance (positive means blocked).
+    // The goal is always walkable, even if it's tight against an obstacle.
+    let depth = |cell: Cell| -> f32 {
+        if cell == goal_cell {
+            return 0.0;
+        }
+        let position = to_position(cell);
+        obstacles.iter()
+            .map(|obstacle| obstacle.radius + settings.clearance - position.xz().distance(obstacle.position.xz()))
+            .fold(f32::NEG_INFINITY, f32::max)
+    };
+    let blocked = |cell: Cell| depth(cell) > 0.0;
+    let heuristic = |cell: Cell| -> f32 {
+        Vec2::new((cell.0 - goal_cell.0) as f32, (cell.1 - goal_cell.1) as f32).length()
+    };
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut cost: HashMap<Cell, f32> = HashMap::new();
+    open.push(OpenCell { cell: start_cell, estimate: heuristic(start_cell) });
+    cost.insert(start_cell, 0.0);
+
+    let mut explored = 0;
+    while let Some(OpenCell { cell, .. }) = open.pop() {
+        if cell == goal_cell {
+            // Walk back to the start, then drop waypoints that can be skipped in a straight line
+            let mut cells = vec![cell];
+            let mut current = cell;
+            while let Some(&previous) = came_from.get(&current) {
+                cells.push(previous);
+                current = previous;
+            }
+            cells.reverse();
+
+            let mut waypoints: Vec<Vec3> = cells.into_iter().skip(1).map(to_position).collect();
+            if let Some(last) = waypoints.last_mut() {
+                *last = goal;
+            }
+            return Some(smooth_path(start, waypoints, obstacles, settings.clearance));
+        }
+
+        explored += 1;
+        if explored > settings.max_nodes {
+            return None;
+        }
+
+        let current_cost = cost[&cell];
+        let current_depth = depth(cell);
+        for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let neighbour = (cell.0 + dx, cell.1 + dz);
+            // A walker that starts pushed into an obstacle may only move further out of it
+            let neighbour_depth = depth(neighbour);
+            if neighbour_depth > 0.0 && neighbour_depth >= current_depth {
+                continue;
+            }
+            // Don't cut corners past obstacles when moving diagonally
+            if dx != 0 && dz != 0 && current_depth <= 0.0 && (blocked((cell.0 + dx, cell.1)) || blocked((cell.0, cell.1 + dz))) {
+                continue;
+            }
+
+            let step = if dx != 0 && dz != 0 { std::f32::consts::SQRT_2 } else { 1.0 };
+            let new_cost = current_cost + step;
+            if cost.get(&neighbour).map_or(true, |&existing| new_cost < existing) {
+                cost.insert(neighbour, new_cost);
+                came_from.insert(neighbour, cell);
+                open.push(OpenCell { cell: neighbour, estimate: new_cost + heuristic(neighbour) });
+            }
+        }
+    }
+
+    None
+}
+
+// Skip waypoints that can be reached directly from an earlier point
+fn smooth_path(start: Vec3, waypoints: Vec<Vec3>, obstacles: &[Obstacle], clearance: f32) -> Vec<Vec3> {
+    let mut smoothed = Vec::new();
+    let mut from = start;
+    let mut index = 0;
+
+    while index < waypoints.len() {
+        // Furthest waypoint visible from the current point
+        let mut furthest = index;
+        for candidate in (index + 1)..waypoints.len() {
+            if line_is_clear(from, waypoints[candidate], obstacles, clearance) {
+                furthest = candidate;
+            }
+        }
+        smoothed.push(waypoints[furthest]);
+        from = waypoints[furthest];
+        index = furthest + 1;
+    }
+
+    smoothed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: PathSettings = PathSettings {
+        cell_size: 0.5,
+        clearance: 0.5,
+        max_nodes: 2000,
+    };
+
+    fn obstacle(x: f32, z: f32, radius: f32) -> Obstacle {
+        Obstacle {
+            position: Vec3::new(x, 0.0, z),
+            radius,
+        }
+    }
+
+    #[test]
+    fn clear_path_goes_straight_to_the_goal() {
+        let goal = Vec3::new(10.0, 0.0, 0.0);
+        assert_eq!(find_path(Vec3::ZERO, goal, &[], &SETTINGS), Some(vec![goal]));
+    }
+
+    #[test]
+    fn path_routes_around_an_obstacle() {
+        let start = Vec3::ZERO;
+        let goal = Vec3::new(10.0, 0.0, 0.0);
+        let obstacles = [obstacle(5.0, 0.0, 1.0)];
+
+        let path = find_path(start, goal, &obstacles, &SETTINGS).expect("a path around the obstacle");
+        assert!(path.len() > 1, "the path should turn to get around the obstacle");
+        assert_eq!(path.last(), Some(&goal));
+
+        // No leg of the path walks through the obstacle itself
+        let mut from = start;
+        for &waypoint in &path {
+            assert!(line_is_clear(from, waypoint, &obstacles, 0.0), "{:?} to {:?} crosses the obstacle", from, waypoint);
+            from = waypoint;
+        }
+    }
+
+    #[test]
+    fn unreachable_goal_gives_up_within_the_search_limit() {
+        // A ring of obstacles walls the goal in
+        let goal = Vec3::new(10.0, 0.0, 0.0);
+        let obstacles: Vec<Obstacle> = (0..24)
+            .map(|index| {
+                let angle = index as f32 / 24.0 * std::f32::consts::TAU;
+                obstacle(goal.x + angle.cos() * 3.0, goal.z + angle.sin() * 3.0, 1.0)
+            })
+            .collect();
+        let settings = PathSettings { max_nodes: 300, ..SETTINGS };
+
+        assert_eq!(find_path(Vec3::ZERO, goal, &obstacles, &settings), None);
+    }
+
+    #[test]
+    fn walker_inside_an_obstacle_finds_its_way_out() {
+        let goal = Vec3::new(10.0, 0.0, 0.0);
+        let obstacles = [obstacle(0.0, 0.0, 1.0)];
+
+        let path = find_path(Vec3::ZERO, goal, &obstacles, &SETTINGS).expect("a path out of the obstacle");
+        assert_eq!(path.last(), Some(&goal));
+    }
+}
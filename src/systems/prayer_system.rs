@@ -0,0 +1,329 @@
+use bevy::prelude::*;
+use std::collections::HashSet;
+use crate::shared::components::Skills;
+use crate::client::input::Player;
+use crate::client::terrain::{WorldObject, nearest_world_object};
+use crate::systems::combat_system::CombatStyle;
+use crate::systems::experience::level_for_experience;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, UseItemEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Where the village chapel's altar stands
+const CHAPEL_ALTAR_POSITION: Vec3 = Vec3::new(3.0, 0.0, -8.0);
+
+pub struct PrayerPlugin;
+
+impl Plugin for PrayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PrayerDatabase>()
+           .add_event::<TogglePrayerEvent>()
+           .add_systems(Startup, spawn_chapel_altar)
+           .add_systems(Update, (
+               bury_bones,
+               toggle_prayers,
+               drain_prayer_points,
+               recharge_at_altar,
+           ));
+    }
+}
+
+// Bones that can be buried
+#[derive(Clone, Debug)]
+pub struct BoneDefinition {
+    pub item_id: u64,
+    pub experience: u32,
+}
+
+// What an active prayer does
+#[derive(Clone, Debug)]
+pub enum PrayerEffect {
+    // Raise the effective level of a combat skill by a percentage
+    Boost { skill: &'static str, percent: f32 },
+    // Block all damage from enemy attacks of one style
+    Protect(CombatStyle),
+}
+
+#[derive(Clone, Debug)]
+pub struct PrayerDefinition {
+    pub name: String,
+    pub level_requirement: u32,
+    // Prayer points drained per minute while active
+    pub drain_per_minute: f32,
+    pub effect: PrayerEffect,
+}
+
+impl PrayerDefinition {
+    // Prayers that can't be active together (same boosted skill, or two protection prayers)
+    fn conflicts_with(&self, other: &PrayerDefinition) -> bool {
+        match (&self.effect, &other.effect) {
+            (PrayerEffect::Boost { skill, .. }, PrayerEffect::Boost { skill: other_skill, .. }) => skill == other_skill,
+            (PrayerEffect::Protect(_), PrayerEffect::Protect(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+// Resource for bones and prayers (a prayer's ID is its index)
+#[derive(Resource)]
+pub struct PrayerDatabase {
+    pub bones: Vec<BoneDefinition>,
+    pub prayers: Vec<PrayerDefinition>,
+    // Experience multiplier for offering bones at an altar instead of burying them
+    pub altar_multiplier: f32,
+}
+
+impl Default for PrayerDatabase {
+    fn default() -> Self {
+        let bones = vec![
+            BoneDefinition { item_id: 190, experience: 5 },
+            BoneDefinition { item_id: 191, experience: 15 },
+            BoneDefinition { item_id: 192, experience: 72 },
+        ];
+
+        let prayers = vec![
+            prayer("Thick Skin", 1, 3.0, PrayerEffect::Boost { skill: "defense", percent: 0.05 }),
+            prayer("Burst of Strength", 4, 3.0, PrayerEffect::Boost { skill: "strength", percent: 0.05 }),
+            prayer("Clarity of Thought", 7, 3.0, PrayerEffect::Boost { skill: "attack", percent: 0.05 }),
+            prayer("Sharp Eye", 8, 3.0, PrayerEffect::Boost { skill: "ranged", percent: 0.05 }),
+            prayer("Mystic Will", 9, 3.0, PrayerEffect::Boost { skill: "magic", percent: 0.05 }),
+            prayer("Rock Skin", 10, 6.0, PrayerEffect::Boost { skill: "defense", percent: 0.1 }),
+            prayer("Superhuman Strength", 13, 6.0, PrayerEffect::Boost { skill: "strength", percent: 0.1 }),
+            prayer("Improved Reflexes", 16, 6.0, PrayerEffect::Boost { skill: "attack", percent: 0.1 }),
+            prayer("Protect from Magic", 37, 12.0, PrayerEffect::Protect(CombatStyle::Magic)),
+            prayer("Protect from Missiles", 40, 12.0, PrayerEffect::Protect(CombatStyle::Ranged)),
+            prayer("Protect from Melee", 43, 12.0, PrayerEffect::Protect(CombatStyle::Melee)),
+        ];
+
+        Self {
+            bones,
+            prayers,
+            altar_multiplier: 3.5,
+        }
+    }
+}
+
+// Helper to define a prayer
+fn prayer(name: &str, level_requirement: u32, drain_per_minute: f32, effect: PrayerEffect) -> PrayerDefinition {
+    PrayerDefinition {
+        name: name.to_string(),
+        level_requirement,
+        drain_per_minute,
+        effect,
+    }
+}
+
+impl PrayerDatabase {
+    pub fn bone_for_item(&self, item_id: u64) -> Option<&BoneDefinition> {
+        self.bones.iter().find(|bone| bone.item_id == item_id)
+    }
+}
+
+// Player prayer points; the maximum is the prayer level
+#[derive(Component)]
+pub struct PrayerPoints {
+    pub current: f32,
+}
+
+impl Default for PrayerPoints {
+    fn default() -> Self {
+        Self { current: 1.0 }
+    }
+}
+
+// Prayers the player currently has switched on
+#[derive(Component, Default)]
+pub struct ActivePrayers {
+    pub active: HashSet<usize>,
+}
+
+// Multiplier active prayers apply to a combat skill's effective level
+pub fn prayer_multiplier(prayers: Option<&ActivePrayers>, database: &PrayerDatabase, skill_name: &str) -> f32 {
+    let Some(prayers) = prayers else {
+        return 1.0;
+    };
+    1.0 + prayers.active.iter()
+        .filter_map(|&id| database.prayers.get(id))
+        .map(|prayer| match prayer.effect {
+            PrayerEffect::Boost { skill, percent } if skill == skill_name => percent,
+            _ => 0.0,
+        })
+        .sum::<f32>()
+}
+
+// Whether active prayers block attacks of this style
+pub fn protects_from(prayers: Option<&ActivePrayers>, database: &PrayerDatabase, style: CombatStyle) -> bool {
+    prayers.map_or(false, |prayers| {
+        prayers.active.iter()
+            .filter_map(|&id| database.prayers.get(id))
+            .any(|prayer| matches!(prayer.effect, PrayerEffect::Protect(protected) if protected == style))
+    })
+}
+
+// Event for switching a prayer on or off
+#[derive(Event)]
+pub struct TogglePrayerEvent {
+    pub prayer_id: usize,
+}
+
+// Spawn the altar in the village chapel
+fn spawn_chapel_altar(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(2.0, 1.0, 1.0))),
+            material: materials.add(Color::rgb(0.9, 0.9, 0.85).into()),
+            transform: Transform::from_translation(CHAPEL_ALTAR_POSITION + Vec3::Y * 0.5),
+            ..default()
+        },
+        WorldObject::PrayerAltar,
+    )).with_children(|parent| {
+        // Altar cloth
+        parent.spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Box::new(2.05, 0.05, 0.6))),
+            material: materials.add(Color::rgb(0.6, 0.1, 0.15).into()),
+            transform: Transform::from_xyz(0.0, 0.51, 0.0),
+            ..default()
+        });
+    });
+}
+
+// Use bones alone to bury them, or offer them for extra XP while standing at an altar
+fn bury_bones(
+    mut commands: Commands,
+    mut events: EventReader<UseItemEvent>,
+    database: Res<PrayerDatabase>,
+    player_query: Query<(&Transform, &Inventory), With<Player>>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((player_transform, inventory)) = player_query.get_single() else {
+        return;
+    };
+
+    for event in events.read() {
+        if event.target_item_id.is_some() || !inventory.items.contains_key(&event.item_id) {
+            continue;
+        }
+        let Some(bone) = database.bone_for_item(event.item_id) else {
+            continue;
+        };
+
+        let at_altar = nearest_world_object(player_transform.translation, object_query.iter(), |object| *object == WorldObject::PrayerAltar).is_some();
+        let (experience, message) = if at_altar {
+            ((bone.experience as f32 * database.altar_multiplier) as u32, "The gods are pleased with your offering.")
+        } else {
+            (bone.experience, "You bury the bones.")
+        };
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: bone.item_id,
+            quantity: -1,
+        });
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "prayer".to_string(),
+            experience,
+        });
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("{} +{} prayer XP", message, experience),
+            Color::rgb(0.9, 0.9, 0.6),
+        );
+    }
+}
+
+// Switch prayers on and off, turning off any that conflict
+fn toggle_prayers(
+    mut events: EventReader<TogglePrayerEvent>,
+    database: Res<PrayerDatabase>,
+    mut player_query: Query<(&Skills, &PrayerPoints, &mut ActivePrayers), With<Player>>,
+) {
+    let Ok((skills, points, mut prayers)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in events.read() {
+        let Some(definition) = database.prayers.get(event.prayer_id) else {
+            continue;
+        };
+
+        if prayers.active.remove(&event.prayer_id) {
+            continue;
+        }
+
+        if level_for_experience(skills.prayer) < definition.level_requirement {
+            println!("You need a prayer level of {} to use {}.", definition.level_requirement, definition.name);
+            continue;
+        }
+        if points.current <= 0.0 {
+            println!("You need to recharge your prayer points at an altar.");
+            continue;
+        }
+
+        prayers.active.retain(|&id| !database.prayers.get(id).is_some_and(|other| definition.conflicts_with(other)));
+        prayers.active.insert(event.prayer_id);
+    }
+}
+
+// Drain prayer points while prayers are active, switching them all off once empty
+fn drain_prayer_points(
+    database: Res<PrayerDatabase>,
+    mut player_query: Query<(&Skills, &mut PrayerPoints, &mut ActivePrayers), With<Player>>,
+    time: Res<Time>,
+) {
+    for (skills, mut points, mut prayers) in player_query.iter_mut() {
+        let maximum = level_for_experience(skills.prayer) as f32;
+        points.current = points.current.min(maximum);
+
+        if prayers.active.is_empty() {
+            continue;
+        }
+
+        let drain_per_minute: f32 = prayers.active.iter()
+            .filter_map(|&id| database.prayers.get(id))
+            .map(|prayer| prayer.drain_per_minute)
+            .sum();
+        points.current = (points.current - drain_per_minute * time.delta_seconds() / 60.0).max(0.0);
+
+        if points.current == 0.0 {
+            prayers.active.clear();
+            println!("You have run out of prayer points, you can recharge at an altar.");
+        }
+    }
+}
+
+// Press F at an altar to recharge prayer points
+fn recharge_at_altar(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    mut player_query: Query<(&Transform, &Skills, &mut PrayerPoints), With<Player>>,
+    object_query: Query<&WorldObject>,
+) {
+    let at_altar = interact_events.read().last()
+        .is_some_and(|event| object_query.get(event.target).is_ok_and(|object| *object == WorldObject::PrayerAltar));
+    if !at_altar {
+        return;
+    }
+    let Ok((player_transform, skills, mut points)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let maximum = level_for_experience(skills.prayer) as f32;
+    let message = if points.current < maximum {
+        points.current = maximum;
+        "You recharge your prayer points."
+    } else {
+        "You already have full prayer points."
+    };
+    spawn_floating_text(
+        &mut commands,
+        player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+        message.to_string(),
+        Color::rgb(0.9, 0.9, 0.6),
+    );
+}
@@ -0,0 +1,330 @@
+use bevy::prelude::*;
+use crate::shared::components::Skills;
+use crate::client::input::Player;
+use crate::systems::herblore_system::{SkillBoosts, boosted_level};
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent, UseItemEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Item IDs of the production tools
+pub const KNIFE_ITEM_ID: u64 = 33;
+pub const NEEDLE_ITEM_ID: u64 = 34;
+pub const CHISEL_ITEM_ID: u64 = 35;
+
+pub struct ProductionPlugin;
+
+impl Plugin for ProductionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProductionDatabase>()
+           .init_resource::<ProductionMenu>()
+           .add_event::<StartProductionEvent>()
+           .add_systems(Update, (
+               open_production_menu,
+               start_production,
+               process_production,
+           ));
+    }
+}
+
+// Choose a product and how many to make (sent from the make-X dialog)
+#[derive(Event)]
+pub struct StartProductionEvent {
+    pub product_item_id: u64,
+    pub quantity: u32,
+}
+
+// Products offered by the make-X dialog after using one item on another
+#[derive(Resource)]
+pub struct ProductionMenu {
+    pub options: Vec<u64>, // product item IDs, empty while closed
+    pub custom_quantity: u32,
+}
+
+impl Default for ProductionMenu {
+    fn default() -> Self {
+        Self {
+            options: Vec::new(),
+            custom_quantity: 10,
+        }
+    }
+}
+
+impl ProductionMenu {
+    pub fn is_open(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    pub fn close(&mut self) {
+        self.options.clear();
+    }
+}
+
+// Batched production state (progress counts towards the next item being made)
+#[derive(Component)]
+pub struct ProductionInProgress {
+    pub product_item_id: u64,
+    pub remaining: u32,
+    pub progress: f32,
+    pub total_time: f32,
+}
+
+// A tool used on a material to make something (knife on logs, needle on leather, ...)
+#[derive(Clone, Debug)]
+pub struct ProductionRecipe {
+    pub tool_item_id: u64,
+    // Mixing recipes use up the "tool" as an ingredient too (herb into a vial of water)
+    pub consumes_tool: bool,
+    pub material_item_id: u64,
+    pub material_quantity: u32,
+    pub product_item_id: u64,
+    pub product_quantity: u32,
+    pub skill_name: &'static str,
+    pub level_requirement: u32,
+    pub experience: u32,
+}
+
+// Resource for use-item-on-item recipes (kept in display order)
+#[derive(Resource)]
+pub struct ProductionDatabase {
+    pub recipes: Vec<ProductionRecipe>,
+    pub production_time: f32,
+}
+
+impl Default for ProductionDatabase {
+    fn default() -> Self {
+        let mut recipes = Vec::new();
+
+        // Knife on logs (fletching)
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (1, 1), (90, 15), "fletching", 1, 5);   // Arrow shafts
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (1, 1), (91, 1), "fletching", 5, 5);    // Shortbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (1, 1), (92, 1), "fletching", 10, 10);  // Longbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (10, 1), (93, 1), "fletching", 20, 16); // Oak shortbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (10, 1), (94, 1), "fletching", 25, 25); // Oak longbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (11, 1), (95, 1), "fletching", 35, 33); // Willow shortbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (11, 1), (96, 1), "fletching", 40, 41); // Willow longbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (12, 1), (97, 1), "fletching", 65, 67); // Yew shortbow
+        insert_recipe(&mut recipes, KNIFE_ITEM_ID, (12, 1), (98, 1), "fletching", 70, 75); // Yew longbow
+
+        // Needle on leather (crafting)
+        insert_recipe(&mut recipes, NEEDLE_ITEM_ID, (83, 1), (100, 1), "crafting", 1, 13);  // Leather gloves
+        insert_recipe(&mut recipes, NEEDLE_ITEM_ID, (83, 1), (101, 1), "crafting", 7, 16);  // Leather boots
+        insert_recipe(&mut recipes, NEEDLE_ITEM_ID, (83, 1), (102, 1), "crafting", 14, 25); // Leather body
+
+        // Chisel on uncut gems (crafting)
+        insert_recipe(&mut recipes, CHISEL_ITEM_ID, (84, 1), (87, 1), "crafting", 20, 50); // Sapphire
+        insert_recipe(&mut recipes, CHISEL_ITEM_ID, (85, 1), (88, 1), "crafting", 27, 67); // Emerald
+        insert_recipe(&mut recipes, CHISEL_ITEM_ID, (86, 1), (89, 1), "crafting", 34, 85); // Ruby
+
+        // Herbs into vials of water, then secondaries into the unfinished potion (herblore)
+        insert_mixing_recipe(&mut recipes, 115, 110, 116, 3, 0);  // Guam potion (unf)
+        insert_mixing_recipe(&mut recipes, 115, 111, 117, 12, 0); // Tarromin potion (unf)
+        insert_mixing_recipe(&mut recipes, 115, 112, 118, 22, 0); // Harralander potion (unf)
+        insert_mixing_recipe(&mut recipes, 115, 113, 119, 30, 0); // Ranarr potion (unf)
+        insert_mixing_recipe(&mut recipes, 116, 120, 131, 3, 25);  // Attack potion(3)
+        insert_mixing_recipe(&mut recipes, 117, 121, 135, 12, 50); // Strength potion(3)
+        insert_mixing_recipe(&mut recipes, 118, 122, 139, 22, 62); // Restore potion(3)
+        insert_mixing_recipe(&mut recipes, 119, 123, 143, 30, 75); // Defence potion(3)
+
+        Self {
+            recipes,
+            production_time: 1.8,
+        }
+    }
+}
+
+impl ProductionDatabase {
+    pub fn recipe_for_product(&self, product_item_id: u64) -> Option<&ProductionRecipe> {
+        self.recipes.iter().find(|recipe| recipe.product_item_id == product_item_id)
+    }
+
+    // Recipes for a pair of items, in either order (knife on logs or logs on knife)
+    pub fn recipes_for_pair(&self, first: u64, second: u64) -> Vec<&ProductionRecipe> {
+        self.recipes.iter()
+            .filter(|recipe| {
+                (recipe.tool_item_id == first && recipe.material_item_id == second)
+                    || (recipe.tool_item_id == second && recipe.material_item_id == first)
+            })
+            .collect()
+    }
+}
+
+// How many times a recipe can be made from the materials in the inventory
+pub fn max_batches(inventory: &Inventory, recipe: &ProductionRecipe) -> u32 {
+    let tools = inventory.items.get(&recipe.tool_item_id).copied().unwrap_or(0);
+    if tools == 0 {
+        return 0;
+    }
+
+    let batches = inventory.items.get(&recipe.material_item_id).copied().unwrap_or(0) / recipe.material_quantity;
+    if recipe.consumes_tool {
+        batches.min(tools)
+    } else {
+        batches
+    }
+}
+
+// Open the make-X dialog when a tool is used on a material it works on
+fn open_production_menu(
+    mut events: EventReader<UseItemEvent>,
+    database: Res<ProductionDatabase>,
+    mut menu: ResMut<ProductionMenu>,
+) {
+    for event in events.read() {
+        let Some(target_item_id) = event.target_item_id else {
+            continue;
+        };
+
+        let recipes = database.recipes_for_pair(event.item_id, target_item_id);
+        if recipes.is_empty() {
+            continue;
+        }
+
+        menu.options = recipes.iter().map(|recipe| recipe.product_item_id).collect();
+    }
+}
+
+// Begin making the chosen product (replacing anything already being made)
+fn start_production(
+    mut commands: Commands,
+    mut events: EventReader<StartProductionEvent>,
+    database: Res<ProductionDatabase>,
+    player_query: Query<(Entity, &Transform, &Skills, Option<&SkillBoosts>, &Inventory), With<Player>>,
+    mut menu: ResMut<ProductionMenu>,
+) {
+    let Ok((player_entity, player_transform, skills, boosts, inventory)) = player_query.get_single() else {
+        return;
+    };
+
+    for event in events.read() {
+        menu.close();
+
+        let Some(recipe) = database.recipe_for_product(event.product_item_id) else {
+            continue;
+        };
+
+        // Potion boosts count towards the level requirement
+        if boosted_level(skills, boosts, recipe.skill_name) < recipe.level_requirement {
+            let message = format!("You need a {} level of {} to make this.", recipe.skill_name, recipe.level_requirement);
+            println!("{}", message);
+            spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+            continue;
+        }
+
+        let remaining = event.quantity.min(max_batches(inventory, recipe));
+        if remaining == 0 {
+            println!("You don't have the materials to make this.");
+            continue;
+        }
+
+        commands.entity(player_entity).insert(ProductionInProgress {
+            product_item_id: recipe.product_item_id,
+            remaining,
+            progress: 0.0,
+            total_time: database.production_time,
+        });
+    }
+}
+
+// Make one batch per production tick until the quantity is reached or materials run out
+fn process_production(
+    mut commands: Commands,
+    database: Res<ProductionDatabase>,
+    mut player_query: Query<(Entity, &Transform, &Inventory, &mut ProductionInProgress), With<Player>>,
+    time: Res<Time>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    for (entity, player_transform, inventory, mut production) in player_query.iter_mut() {
+        let Some(recipe) = database.recipe_for_product(production.product_item_id) else {
+            commands.entity(entity).remove::<ProductionInProgress>();
+            continue;
+        };
+
+        if max_batches(inventory, recipe) == 0 || !inventory.has_room_for(recipe.product_item_id) {
+            commands.entity(entity).remove::<ProductionInProgress>();
+            continue;
+        }
+
+        production.progress += time.delta_seconds();
+        if production.progress < production.total_time {
+            continue;
+        }
+        production.progress -= production.total_time;
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: recipe.material_item_id,
+            quantity: -(recipe.material_quantity as i32),
+        });
+        if recipe.consumes_tool {
+            inventory_events.send(InventoryUpdateEvent {
+                item_id: recipe.tool_item_id,
+                quantity: -1,
+            });
+        }
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: recipe.product_item_id,
+            quantity: recipe.product_quantity as i32,
+        });
+        if recipe.experience > 0 {
+            skill_events.send(SkillExperienceEvent {
+                skill_name: recipe.skill_name.to_string(),
+                experience: recipe.experience,
+            });
+            spawn_floating_text(
+                &mut commands,
+                player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+                format!("+{} {} XP", recipe.experience, recipe.skill_name),
+                Color::rgb(0.9, 0.9, 0.1),
+            );
+        }
+
+        production.remaining = production.remaining.saturating_sub(1);
+        if production.remaining == 0 {
+            commands.entity(entity).remove::<ProductionInProgress>();
+        }
+    }
+}
+
+// Helper to add a production recipe to the database
+fn insert_recipe(
+    recipes: &mut Vec<ProductionRecipe>,
+    tool_item_id: u64,
+    (material_item_id, material_quantity): (u64, u32),
+    (product_item_id, product_quantity): (u64, u32),
+    skill_name: &'static str,
+    level_requirement: u32,
+    experience: u32,
+) {
+    recipes.push(ProductionRecipe {
+        tool_item_id,
+        consumes_tool: false,
+        material_item_id,
+        material_quantity,
+        product_item_id,
+        product_quantity,
+        skill_name,
+        level_requirement,
+        experience,
+    });
+}
+
+// Helper to add a herblore mixing recipe, where both ingredients are used up
+fn insert_mixing_recipe(
+    recipes: &mut Vec<ProductionRecipe>,
+    base_item_id: u64,
+    ingredient_item_id: u64,
+    product_item_id: u64,
+    level_requirement: u32,
+    experience: u32,
+) {
+    recipes.push(ProductionRecipe {
+        tool_item_id: base_item_id,
+        consumes_tool: true,
+        material_item_id: ingredient_item_id,
+        material_quantity: 1,
+        product_item_id,
+        product_quantity: 1,
+        skill_name: "herblore",
+        level_requirement,
+        experience,
+    });
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use crate::shared::components::Health;
+use crate::client::input::Player;
+use crate::client::physics::Collider;
+use crate::systems::combat_system::{CombatStyle, DamageEvent, Enemy, PendingHit, deal_hit};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectileSettings>()
+           .add_systems(Update, update_projectiles);
+    }
+}
+
+// Projectile settings
+#[derive(Resource)]
+pub struct ProjectileSettings {
+    pub arrow_speed: f32,
+    pub spell_speed: f32,
+    // Height above an entity's position that projectiles leave from and aim at
+    pub aim_height: f32,
+}
+
+impl Default for ProjectileSettings {
+    fn default() -> Self {
+        Self {
+            arrow_speed: 14.0,
+            spell_speed: 9.0,
+            aim_height: 0.5,
+        }
+    }
+}
+
+// What a projectile looks like
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProjectileKind {
+    Arrow,
+    SpellOrb { color: Color },
+}
+
+// A ranged or magic attack in flight, dealing its hit when it reaches the target
+#[derive(Component)]
+pub struct Projectile {
+    pub kind: ProjectileKind,
+    pub hit: PendingHit,
+    pub speed: f32,
+}
+
+// Launch a projectile carrying a rolled hit from the attacker towards the target
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    settings: &ProjectileSettings,
+    origin: Vec3,
+    hit: PendingHit,
+    kind: ProjectileKind,
+) {
+    let speed = match hit.style {
+        CombatStyle::Magic => settings.spell_speed,
+        _ => settings.arrow_speed,
+    };
+
+    commands.spawn((
+        SpatialBundle {
+            transform: Transform::from_translation(origin + Vec3::Y * settings.aim_height),
+            ..default()
+        },
+        Projectile {
+            kind,
+            hit,
+            speed,
+        },
+    ));
+}
+
+// Fly projectiles after their target; they land on arrival, or break on anything in the way
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn update_projectiles(
+    mut commands: Commands,
+    settings: Res<ProjectileSettings>,
+    mut projectile_query: Query<(Entity, &mut Transform, &Projectile)>,
+    target_query: Query<(&Transform, &Health), Without<Projectile>>,
+    obstacle_query: Query<(&Transform, &Collider), (Without<Enemy>, Without<Player>, Without<Projectile>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, projectile) in projectile_query.iter_mut() {
+        let Ok((target_transform, target_health)) = target_query.get(projectile.hit.target) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let aim = target_transform.translation + Vec3::Y * settings.aim_height;
+        let to_target = aim - transform.translation;
+        let step = projectile.speed * time.delta_seconds();
+
+        if to_target.length() <= step {
+            deal_hit(&projectile.hit, Some(target_health), &mut damage_events, &mut skill_events);
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation += to_target.normalize() * step;
+        transform.look_at(aim, Vec3::Y);
+
+        // Targets that duck behind a tree, rock or building leave the projectile to hit that instead
+        let position = transform.translation;
+        let blocked = obstacle_query.iter().any(|(obstacle_transform, collider)| {
+            let top = obstacle_transform.translation.y + collider.height / 2.0;
+            let horizontal_distance = Vec2::new(position.x - obstacle_transform.translation.x, position.z - obstacle_transform.translation.z).length();
+            horizontal_distance < collider.radius && position.y < top
+        });
+        if blocked {
+            if projectile.hit.is_player_source {
+                spawn_floating_text(&mut commands, position + Vec3::Y, "Blocked!".to_string(), Color::rgb(0.7, 0.7, 0.7));
+            }
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
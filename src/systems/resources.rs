@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::client::terrain::ResourceNodeType;
+use crate::systems::inventory_system::Inventory;
+use crate::systems::skills_system::GatheringInProgress;
+
+pub struct ResourcesPlugin;
+
+impl Plugin for ResourcesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ResourceDatabase>()
+           .add_event::<ResourceDepletedEvent>()
+           .add_event::<ResourceRespawnedEvent>()
+           .add_systems(Update, (
+               interrupt_gathering_on_depleted,
+               respawn_depleted_resources,
+           ));
+    }
+}
+
+// Marks a resource node as depleted until its respawn timer runs out
+#[derive(Component)]
+pub struct DepletedResource {
+    pub respawn_timer: Timer,
+}
+
+// Sent when a node runs out (tree cut down, rock mined out, fishing spot moved on)
+#[derive(Event)]
+pub struct ResourceDepletedEvent {
+    pub entity: Entity,
+}
+
+// Sent when a depleted node becomes gatherable again
+#[derive(Event)]
+pub struct ResourceRespawnedEvent {
+    pub entity: Entity,
+}
+
+// Component linking a resource node in the world to its definition
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ResourceNode {
+    pub definition_id: u32,
+}
+
+// Tools needed to gather from a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolType {
+    Axe,
+    Pickaxe,
+    FishingNet,
+    FishingRod,
+    LobsterPot,
+}
+
+impl ToolType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ToolType::Axe => "an axe",
+            ToolType::Pickaxe => "a pickaxe",
+            ToolType::FishingNet => "a small fishing net",
+            ToolType::FishingRod => "a fishing rod",
+            ToolType::LobsterPot => "a lobster pot",
+        }
+    }
+}
+
+// Definition of a resource tier (e.g. oak tree, iron ore, lobster spot)
+#[derive(Clone, Debug)]
+pub struct ResourceDefinition {
+    pub id: u32,
+    pub name: String,
+    pub node_type: ResourceNodeType,
+    pub skill_name: &'static str,
+    pub level_requirement: u32,
+    pub tool_type: ToolType,
+    pub experience: u32,
+    pub product_item_id: u64,
+    // Chance of a successful gathering attempt at level 1 and level 99 (with a basic tool)
+    pub success_low: f32,
+    pub success_high: f32,
+    // Chance each successful gather depletes the node, and seconds until it respawns
+    pub depletion_chance: f32,
+    pub respawn_time: f32,
+    // Relative chance of this tier being placed when the world is generated
+    pub spawn_weight: u32,
+    pub color: Color,
+}
+
+// Definition of a gathering tool item
+#[derive(Clone, Debug)]
+pub struct ToolDefinition {
+    #[allow(dead_code)]
+    pub item_id: u64,
+    pub tool_type: ToolType,
+    pub level_requirement: u32,
+    // Higher power tools succeed more often
+    pub power: f32,
+}
+
+// Resource for all resource tiers and gathering tools
+#[derive(Resource)]
+pub struct ResourceDatabase {
+    pub resources: HashMap<u32, ResourceDefinition>,
+    pub tools: HashMap<u64, ToolDefinition>,
+}
+
+impl Default for ResourceDatabase {
+    fn default() -> Self {
+        let mut resources = HashMap::new();
+
+        // Trees (woodcutting)
+        insert_resource(&mut resources, 1, "Tree", ResourceNodeType::Tree, "woodcutting", 1, ToolType::Axe, 25, 1, (0.25, 0.8), (1.0, 8.0), 50, Color::rgb(0.2, 0.6, 0.2));
+        insert_resource(&mut resources, 2, "Oak tree", ResourceNodeType::Tree, "woodcutting", 15, ToolType::Axe, 37, 10, (0.12, 0.55), (0.125, 14.0), 25, Color::rgb(0.3, 0.5, 0.15));
+        insert_resource(&mut resources, 3, "Willow tree", ResourceNodeType::Tree, "woodcutting", 30, ToolType::Axe, 67, 11, (0.08, 0.4), (0.125, 14.0), 15, Color::rgb(0.5, 0.7, 0.3));
+        insert_resource(&mut resources, 4, "Yew tree", ResourceNodeType::Tree, "woodcutting", 60, ToolType::Axe, 175, 12, (0.02, 0.18), (0.125, 60.0), 10, Color::rgb(0.1, 0.35, 0.15));
+
+        // Plain rocks (mining)
+        insert_resource(&mut resources, 10, "Rocks", ResourceNodeType::Rock, "mining", 1, ToolType::Pickaxe, 5, 2, (0.4, 0.9), (1.0, 3.0), 1, Color::rgb(0.5, 0.5, 0.5));
+
+        // Ore deposits (mining)
+        insert_resource(&mut resources, 11, "Copper rocks", ResourceNodeType::OreDeposit, "mining", 1, ToolType::Pickaxe, 17, 3, (0.35, 0.9), (1.0, 2.4), 30, Color::rgb(0.72, 0.45, 0.2));
+        insert_resource(&mut resources, 12, "Tin rocks", ResourceNodeType::OreDeposit, "mining", 1, ToolType::Pickaxe, 17, 13, (0.35, 0.9), (1.0, 2.4), 30, Color::rgb(0.75, 0.75, 0.7));
+        insert_resource(&mut resources, 13, "Iron rocks", ResourceNodeType::OreDeposit, "mining", 15, ToolType::Pickaxe, 35, 14, (0.2, 0.75), (1.0, 5.4), 20, Color::rgb(0.45, 0.3, 0.25));
+        insert_resource(&mut resources, 14, "Coal rocks", ResourceNodeType::OreDeposit, "mining", 30, ToolType::Pickaxe, 50, 15, (0.08, 0.45), (1.0, 30.0), 15, Color::rgb(0.15, 0.15, 0.15));
+        insert_resource(&mut resources, 15, "Mithril rocks", ResourceNodeType::OreDeposit, "mining", 55, ToolType::Pickaxe, 80, 16, (0.03, 0.25), (1.0, 120.0), 5, Color::rgb(0.3, 0.3, 0.6));
+
+        // Rune essence (mining), which never depletes
+        insert_resource(&mut resources, 16, "Rune essence", ResourceNodeType::EssenceMine, "mining", 1, ToolType::Pickaxe, 5, 175, (0.5, 0.95), (0.0, 1.0), 1, Color::rgb(0.75, 0.7, 0.85));
+
+        // Fishing spots (fishing)
+        insert_resource(&mut resources, 20, "Net fishing spot", ResourceNodeType::FishingSpot, "fishing", 1, ToolType::FishingNet, 10, 4, (0.3, 0.8), (0.05, 20.0), 50, Color::rgba(0.2, 0.4, 0.8, 0.7));
+        insert_resource(&mut resources, 21, "Rod fishing spot", ResourceNodeType::FishingSpot, "fishing", 20, ToolType::FishingRod, 50, 17, (0.15, 0.6), (0.05, 20.0), 30, Color::rgba(0.2, 0.5, 0.7, 0.7));
+        insert_resource(&mut resources, 22, "Cage fishing spot", ResourceNodeType::FishingSpot, "fishing", 40, ToolType::LobsterPot, 90, 18, (0.06, 0.35), (0.05, 30.0), 20, Color::rgba(0.1, 0.3, 0.7, 0.7));
+
+        let mut tools = HashMap::new();
+
+        // Axes
+        insert_tool(&mut tools, 20, ToolType::Axe, 1, 1.0);
+        insert_tool(&mut tools, 21, ToolType::Axe, 1, 1.25);
+        insert_tool(&mut tools, 22, ToolType::Axe, 6, 1.5);
+        insert_tool(&mut tools, 23, ToolType::Axe, 21, 1.75);
+
+        // Pickaxes
+        insert_tool(&mut tools, 24, ToolType::Pickaxe, 1, 1.0);
+        insert_tool(&mut tools, 25, ToolType::Pickaxe, 1, 1.25);
+        insert_tool(&mut tools, 26, ToolType::Pickaxe, 6, 1.5);
+        insert_tool(&mut tools, 27, ToolType::Pickaxe, 21, 1.75);
+
+        // Fishing equipment
+        insert_tool(&mut tools, 28, ToolType::FishingNet, 1, 1.0);
+        insert_tool(&mut tools, 29, ToolType::FishingRod, 1, 1.0);
+        insert_tool(&mut tools, 30, ToolType::LobsterPot, 1, 1.0);
+
+        Self { resources, tools }
+    }
+}
+
+impl ResourceDatabase {
+    // Pick a random tier of the given node type, weighted by spawn_weight
+    pub fn random_definition(&self, node_type: ResourceNodeType, rng: &mut impl Rng) -> Option<&ResourceDefinition> {
+        let mut candidates: Vec<&ResourceDefinition> = self.resources.values()
+            .filter(|definition| definition.node_type == node_type && definition.spawn_weight > 0)
+            .collect();
+        // Keep the order stable so the same roll always picks the same tier
+        candidates.sort_by_key(|definition| definition.id);
+
+        let total_weight: u32 = candidates.iter().map(|definition| definition.spawn_weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total_weight);
+        for definition in candidates {
+            if roll < definition.spawn_weight {
+                return Some(definition);
+            }
+            roll -= definition.spawn_weight;
+        }
+
+        None
+    }
+
+    // Best tool of a type in the inventory that the player has the level to use
+    pub fn best_tool(&self, inventory: &Inventory, tool_type: ToolType, skill_level: u32) -> Option<&ToolDefinition> {
+        inventory.items.keys()
+            .filter_map(|item_id| self.tools.get(item_id))
+            .filter(|tool| tool.tool_type == tool_type && tool.level_requirement <= skill_level)
+            .max_by(|a, b| a.power.total_cmp(&b.power))
+    }
+}
+
+// Stop anyone gathering from a node the moment it depletes
+fn interrupt_gathering_on_depleted(
+    mut commands: Commands,
+    mut events: EventReader<ResourceDepletedEvent>,
+    gathering_query: Query<(Entity, &GatheringInProgress)>,
+) {
+    for event in events.read() {
+        for (entity, gathering) in gathering_query.iter() {
+            if gathering.target_entity == event.entity {
+                commands.entity(entity).remove::<GatheringInProgress>();
+                println!("The resource has been depleted.");
+            }
+        }
+    }
+}
+
+// Tick respawn timers and restore depleted nodes
+fn respawn_depleted_resources(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut DepletedResource)>,
+    time: Res<Time>,
+    mut respawn_events: EventWriter<ResourceRespawnedEvent>,
+) {
+    for (entity, mut depleted) in query.iter_mut() {
+        depleted.respawn_timer.tick(time.delta());
+
+        if depleted.respawn_timer.finished() {
+            commands.entity(entity).remove::<DepletedResource>();
+            respawn_events.send(ResourceRespawnedEvent { entity });
+        }
+    }
+}
+
+// Chance that a single gathering attempt succeeds, based on skill level, node tier and tool quality
+pub fn gathering_success_chance(skill_level: u32, definition: &ResourceDefinition, tool: &ToolDefinition) -> f32 {
+    // Interpolate between the tier's level 1 and level 99 success rates
+    let level = skill_level.clamp(1, 99) as f32;
+    let base_chance = definition.success_low + (definition.success_high - definition.success_low) * (level - 1.0) / 98.0;
+
+    // Better tools improve the odds, but some attempts always fail
+    (base_chance * tool.power).clamp(0.0, 0.95)
+}
+
+// Helper to add a resource tier to the database
+#[allow(clippy::too_many_arguments)]
+fn insert_resource(
+    resources: &mut HashMap<u32, ResourceDefinition>,
+    id: u32,
+    name: &str,
+    node_type: ResourceNodeType,
+    skill_name: &'static str,
+    level_requirement: u32,
+    tool_type: ToolType,
+    experience: u32,
+    product_item_id: u64,
+    (success_low, success_high): (f32, f32),
+    (depletion_chance, respawn_time): (f32, f32),
+    spawn_weight: u32,
+    color: Color,
+) {
+    resources.insert(id, ResourceDefinition {
+        id,
+        name: name.to_string(),
+        node_type,
+        skill_name,
+        level_requirement,
+        tool_type,
+        experience,
+        product_item_id,
+        success_low,
+        success_high,
+        depletion_chance,
+        respawn_time,
+        spawn_weight,
+        color,
+    });
+}
+
+// Helper to add a tool to the database
+fn insert_tool(tools: &mut HashMap<u64, ToolDefinition>, item_id: u64, tool_type: ToolType, level_requirement: u32, power: f32) {
+    tools.insert(item_id, ToolDefinition {
+        item_id,
+        tool_type,
+        level_requirement,
+        power,
+    });
+}
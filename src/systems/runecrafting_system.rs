@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+use crate::shared::components::Skills;
+use crate::client::input::Player;
+use crate::client::terrain::WorldObject;
+use crate::systems::experience::level_for_experience;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Item ID of the rune essence mined from essence rocks
+pub const RUNE_ESSENCE_ITEM_ID: u64 = 175;
+
+pub struct RunecraftingPlugin;
+
+impl Plugin for RunecraftingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunecraftingDatabase>()
+           .add_systems(Startup, spawn_altars)
+           .add_systems(Update, craft_runes);
+    }
+}
+
+// An altar that binds essence into one kind of rune
+#[derive(Clone, Debug)]
+pub struct AltarDefinition {
+    pub name: String,
+    pub rune_item_id: u64,
+    pub level_requirement: u32,
+    pub experience: u32,
+    // Levels at which each essence makes one more rune
+    pub multiple_levels: Vec<u32>,
+    pub position: Vec3,
+    pub color: Color,
+}
+
+impl AltarDefinition {
+    // Runes made from each essence at a runecrafting level
+    pub fn runes_per_essence(&self, runecrafting_level: u32) -> u32 {
+        1 + self.multiple_levels.iter().filter(|&&level| runecrafting_level >= level).count() as u32
+    }
+}
+
+// Resource for runecrafting altars (an altar's ID is its index)
+#[derive(Resource)]
+pub struct RunecraftingDatabase {
+    pub altars: Vec<AltarDefinition>,
+}
+
+impl Default for RunecraftingDatabase {
+    fn default() -> Self {
+        let altars = vec![
+            AltarDefinition {
+                name: "Air altar".to_string(),
+                rune_item_id: 180,
+                level_requirement: 1,
+                experience: 5,
+                multiple_levels: vec![11, 22, 33, 44, 55, 66, 77, 88, 99],
+                position: Vec3::new(-28.0, 0.0, 16.0),
+                color: Color::rgb(0.85, 0.9, 1.0),
+            },
+            AltarDefinition {
+                name: "Water altar".to_string(),
+                rune_item_id: 181,
+                level_requirement: 5,
+                experience: 6,
+                multiple_levels: vec![19, 38, 57, 76, 95],
+                position: Vec3::new(-28.0, 0.0, 28.0),
+                color: Color::rgb(0.2, 0.4, 0.9),
+            },
+            AltarDefinition {
+                name: "Earth altar".to_string(),
+                rune_item_id: 182,
+                level_requirement: 9,
+                experience: 6,
+                multiple_levels: vec![26, 52, 78],
+                position: Vec3::new(-16.0, 0.0, 28.0),
+                color: Color::rgb(0.45, 0.35, 0.2),
+            },
+            AltarDefinition {
+                name: "Fire altar".to_string(),
+                rune_item_id: 183,
+                level_requirement: 14,
+                experience: 7,
+                multiple_levels: vec![35, 70],
+                position: Vec3::new(-16.0, 0.0, 16.0),
+                color: Color::rgb(0.9, 0.3, 0.1),
+            },
+            AltarDefinition {
+                name: "Mind altar".to_string(),
+                rune_item_id: 184,
+                level_requirement: 2,
+                experience: 5,
+                multiple_levels: vec![14, 28, 42, 56, 70, 84, 98],
+                position: Vec3::new(-22.0, 0.0, 10.0),
+                color: Color::rgb(0.9, 0.55, 0.2),
+            },
+            AltarDefinition {
+                name: "Chaos altar".to_string(),
+                rune_item_id: 185,
+                level_requirement: 35,
+                experience: 8,
+                multiple_levels: vec![74],
+                position: Vec3::new(-34.0, 0.0, 22.0),
+                color: Color::rgb(0.6, 0.1, 0.1),
+            },
+            AltarDefinition {
+                name: "Nature altar".to_string(),
+                rune_item_id: 188,
+                level_requirement: 44,
+                experience: 9,
+                multiple_levels: vec![91],
+                position: Vec3::new(-22.0, 0.0, 34.0),
+                color: Color::rgb(0.2, 0.7, 0.2),
+            },
+            AltarDefinition {
+                name: "Law altar".to_string(),
+                rune_item_id: 187,
+                level_requirement: 54,
+                experience: 9,
+                multiple_levels: vec![],
+                position: Vec3::new(-10.0, 0.0, 22.0),
+                color: Color::rgb(0.3, 0.3, 0.9),
+            },
+            AltarDefinition {
+                name: "Death altar".to_string(),
+                rune_item_id: 186,
+                level_requirement: 65,
+                experience: 10,
+                multiple_levels: vec![],
+                position: Vec3::new(-34.0, 0.0, 10.0),
+                color: Color::rgb(0.85, 0.85, 0.85),
+            },
+        ];
+
+        Self { altars }
+    }
+}
+
+// Links an altar in the world to its definition
+#[derive(Component)]
+pub struct RuneAltar {
+    pub altar_id: usize,
+}
+
+// Spawn the rune altars around the essence mine
+fn spawn_altars(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    database: Res<RunecraftingDatabase>,
+) {
+    let base_mesh = meshes.add(Mesh::from(shape::Box::new(1.4, 0.8, 1.4)));
+    let stone_material = materials.add(Color::rgb(0.5, 0.5, 0.55).into());
+    let orb_mesh = meshes.add(Mesh::from(shape::UVSphere {
+        radius: 0.35,
+        sectors: 12,
+        stacks: 12,
+    }));
+
+    for (altar_id, altar) in database.altars.iter().enumerate() {
+        commands.spawn((
+            PbrBundle {
+                mesh: base_mesh.clone(),
+                material: stone_material.clone(),
+                transform: Transform::from_translation(altar.position + Vec3::Y * 0.4),
+                ..default()
+            },
+            WorldObject::RuneAltar,
+            RuneAltar { altar_id },
+        )).with_children(|parent| {
+            // A glowing orb in the altar's element
+            parent.spawn(PbrBundle {
+                mesh: orb_mesh.clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: altar.color,
+                    emissive: altar.color * 0.6,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(0.0, 0.8, 0.0),
+                ..default()
+            });
+        });
+    }
+}
+
+// Press F at an altar to bind all carried essence into runes
+#[allow(clippy::too_many_arguments)]
+fn craft_runes(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    database: Res<RunecraftingDatabase>,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    altar_query: Query<&RuneAltar>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Some(altar) = interact_events.read().last()
+        .and_then(|event| altar_query.get(event.target).ok())
+        .and_then(|altar| database.altars.get(altar.altar_id))
+    else {
+        return;
+    };
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+
+    let runecrafting_level = level_for_experience(skills.runecrafting);
+    if runecrafting_level < altar.level_requirement {
+        let message = format!("You need a runecrafting level of {} to use the {}.", altar.level_requirement, altar.name.to_lowercase());
+        println!("{}", message);
+        spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.3, 0.3));
+        return;
+    }
+
+    let essence = inventory.items.get(&RUNE_ESSENCE_ITEM_ID).copied().unwrap_or(0);
+    if essence == 0 {
+        println!("You need rune essence to bind runes at the {}.", altar.name.to_lowercase());
+        return;
+    }
+
+    // All of the essence is used, so its slot is always free for the runes
+    let runes = essence * altar.runes_per_essence(runecrafting_level);
+    let experience = essence * altar.experience;
+
+    inventory_events.send(InventoryUpdateEvent {
+        item_id: RUNE_ESSENCE_ITEM_ID,
+        quantity: -(essence as i32),
+    });
+    inventory_events.send(InventoryUpdateEvent {
+        item_id: altar.rune_item_id,
+        quantity: runes as i32,
+    });
+    skill_events.send(SkillExperienceEvent {
+        skill_name: "runecrafting".to_string(),
+        experience,
+    });
+    spawn_floating_text(
+        &mut commands,
+        text_position,
+        format!("You bind the temple's power into {} runes. +{} runecrafting XP", runes, experience),
+        Color::rgb(0.7, 0.5, 0.9),
+    );
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::shared::components::Skills;
+use crate::systems::experience::experience_for_level;
 
 pub struct SkillsPlugin;
 
@@ -19,21 +20,13 @@ pub fn gain_experience(_skills: &mut Skills, _skill_name: &str, _experience: u32
     // Experience gain logic will go here
 }
 
-#[allow(dead_code)]
-pub fn calculate_level(_experience: u32) -> u32 {
-    // Level calculation logic will go here
-    // This should follow RuneScape's exponential formula
-    1
-}
-
-// Initialize default skills
-#[allow(dead_code)]
+// Initialize default skills (experience values, hitpoints starts at level 10)
 pub fn new_skills() -> Skills {
     Skills {
         attack: 1,
         defense: 1,
         strength: 1,
-        hitpoints: 10,
+        hitpoints: experience_for_level(10),
         ranged: 1,
         prayer: 1,
         magic: 1,
@@ -1,8 +1,13 @@
 use bevy::prelude::*;
-use crate::shared::components::Skills;
+use rand::Rng;
+use std::collections::HashSet;
+use crate::shared::components::{Skills, SKILL_NAMES};
 use crate::client::input::Player;
 use crate::client::terrain::ResourceNodeType;
-use crate::systems::inventory_system::{InventoryUpdateEvent, get_item_id_for_resource};
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent};
+use crate::systems::resources::{DepletedResource, ResourceDatabase, ResourceDepletedEvent, ResourceNode, gathering_success_chance};
+use crate::systems::experience::{level_for_experience, virtual_level_for_experience};
 
 // Component for floating text effects
 #[derive(Component)]
@@ -19,8 +24,11 @@ impl Plugin for SkillsPlugin {
         app.init_resource::<SkillsSettings>()
            .add_event::<SkillExperienceEvent>()
            .add_event::<ResourceGatheringEvent>()
+           .add_event::<LevelUpEvent>()
+           .add_event::<MilestoneEvent>()
            .add_systems(Update, (
                handle_skill_experience,
+               track_skill_milestones.after(handle_skill_experience),
                handle_resource_gathering,
                check_resource_interaction,
                update_floating_text,
@@ -31,27 +39,28 @@ impl Plugin for SkillsPlugin {
 // Skill settings
 #[derive(Resource)]
 pub struct SkillsSettings {
-    pub experience_curve: Vec<u32>,
-    pub gathering_base_experience: f32,
-    pub gathering_time_base: f32,
+    // Seconds between gathering attempts
+    pub gathering_tick_time: f32,
+    // Show levels past 99 (up to 120) in the skills panel
+    pub virtual_levels: bool,
 }
 
 impl Default for SkillsSettings {
     fn default() -> Self {
-        // Create RuneScape-like experience curve
-        let mut experience_curve = Vec::with_capacity(100);
-        let mut total_xp = 0;
-
-        for level in 1..100 {
-            let points = ((level as f64 - 1.0) + 300.0 * 2.0_f64.powf((level as f64 - 1.0) / 7.0)) / 4.0;
-            total_xp += points.floor() as u32;
-            experience_curve.push(total_xp);
+        Self {
+            gathering_tick_time: 2.4,
+            virtual_levels: false,
         }
+    }
+}
 
-        Self {
-            experience_curve,
-            gathering_base_experience: 10.0,
-            gathering_time_base: 3.0,
+impl SkillsSettings {
+    // Level to show for an amount of experience, respecting the virtual levels option
+    pub fn display_level(&self, experience: u32) -> u32 {
+        if self.virtual_levels {
+            virtual_level_for_experience(experience)
+        } else {
+            level_for_experience(experience)
         }
     }
 }
@@ -63,16 +72,62 @@ pub struct SkillExperienceEvent {
     pub experience: u32,
 }
 
+// Sent when a skill crosses into a new level
+#[derive(Event)]
+pub struct LevelUpEvent {
+    pub skill_name: String,
+    pub old_level: u32,
+    pub new_level: u32,
+}
+
+// Sent the first time a player reaches a milestone
+#[derive(Event)]
+pub struct MilestoneEvent {
+    pub milestone: Milestone,
+    pub broadcast: bool,
+}
+
+// Total level thresholds that count as milestones
+pub const TOTAL_LEVEL_MILESTONES: [u32; 4] = [500, 1000, 1500, 2000];
+
+// Skill milestones tracked for achievements
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Milestone {
+    FirstLevel50,
+    Level99(String),
+    TotalLevel(u32),
+}
+
+impl Milestone {
+    pub fn description(&self) -> String {
+        match self {
+            Milestone::FirstLevel50 => "Reached level 50 in a skill for the first time".to_string(),
+            Milestone::Level99(skill_name) => format!("Reached level 99 {}", skill_name),
+            Milestone::TotalLevel(total) => format!("Reached a total level of {}", total),
+        }
+    }
+}
+
+// Milestones the player has already reached
+#[derive(Component, Default)]
+pub struct SkillMilestones {
+    pub reached: HashSet<Milestone>,
+}
+
 #[derive(Event)]
 pub struct ResourceGatheringEvent {
     pub resource_type: ResourceNodeType,
     pub entity: Entity,
 }
 
-// Resource gathering state
+// How far the player can stand from a node and keep gathering
+pub const GATHERING_RANGE: f32 = 3.5;
+
+// Resource gathering state (progress counts towards the next success roll)
 #[derive(Component)]
 pub struct GatheringInProgress {
     pub resource_type: ResourceNodeType,
+    pub resource_id: u32,
     pub target_entity: Entity,
     pub progress: f32,
     pub total_time: f32,
@@ -82,64 +137,75 @@ pub struct GatheringInProgress {
 // Handle skill experience gain
 fn handle_skill_experience(
     mut events: EventReader<SkillExperienceEvent>,
-    settings: Res<SkillsSettings>,
     mut query: Query<&mut Skills, With<Player>>,
+    mut level_up_events: EventWriter<LevelUpEvent>,
 ) {
     if let Ok(mut skills) = query.get_single_mut() {
         for event in events.read() {
-            match event.skill_name.as_str() {
-                "attack" => skills.attack += event.experience,
-                "defense" => skills.defense += event.experience,
-                "strength" => skills.strength += event.experience,
-                "hitpoints" => skills.hitpoints += event.experience,
-                "ranged" => skills.ranged += event.experience,
-                "prayer" => skills.prayer += event.experience,
-                "magic" => skills.magic += event.experience,
-                "cooking" => skills.cooking += event.experience,
-                "woodcutting" => skills.woodcutting += event.experience,
-                "fletching" => skills.fletching += event.experience,
-                "fishing" => skills.fishing += event.experience,
-                "firemaking" => skills.firemaking += event.experience,
-                "crafting" => skills.crafting += event.experience,
-                "smithing" => skills.smithing += event.experience,
-                "mining" => skills.mining += event.experience,
-                "herblore" => skills.herblore += event.experience,
-                "agility" => skills.agility += event.experience,
-                "thieving" => skills.thieving += event.experience,
-                "slayer" => skills.slayer += event.experience,
-                "farming" => skills.farming += event.experience,
-                "runecrafting" => skills.runecrafting += event.experience,
-                _ => println!("Unknown skill: {}", event.skill_name),
+            let Some(skill_xp) = skills.experience_mut(&event.skill_name) else {
+                println!("Unknown skill: {}", event.skill_name);
+                continue;
+            };
+
+            let old_level = level_for_experience(*skill_xp);
+            *skill_xp = skill_xp.saturating_add(event.experience);
+            let new_level = level_for_experience(*skill_xp);
+
+            println!("Gained {} experience in {}. New level: {}", event.experience, event.skill_name, new_level);
+
+            // Notify everything that cares about crossing a level threshold
+            if new_level > old_level {
+                level_up_events.send(LevelUpEvent {
+                    skill_name: event.skill_name.clone(),
+                    old_level,
+                    new_level,
+                });
+            }
+        }
+    }
+}
+
+// Record skill milestones for achievements and broadcast the notable ones
+fn track_skill_milestones(
+    mut events: EventReader<LevelUpEvent>,
+    mut query: Query<(&Skills, &mut SkillMilestones), With<Player>>,
+    mut milestone_events: EventWriter<MilestoneEvent>,
+) {
+    if let Ok((skills, mut milestones)) = query.get_single_mut() {
+        for event in events.read() {
+            let mut reached = Vec::new();
+
+            if event.new_level >= 50 && !milestones.reached.contains(&Milestone::FirstLevel50) {
+                reached.push(Milestone::FirstLevel50);
             }
 
-            // Calculate and print new level
-            let skill_xp = match event.skill_name.as_str() {
-                "attack" => skills.attack,
-                "defense" => skills.defense,
-                "strength" => skills.strength,
-                "hitpoints" => skills.hitpoints,
-                "ranged" => skills.ranged,
-                "prayer" => skills.prayer,
-                "magic" => skills.magic,
-                "cooking" => skills.cooking,
-                "woodcutting" => skills.woodcutting,
-                "fletching" => skills.fletching,
-                "fishing" => skills.fishing,
-                "firemaking" => skills.firemaking,
-                "crafting" => skills.crafting,
-                "smithing" => skills.smithing,
-                "mining" => skills.mining,
-                "herblore" => skills.herblore,
-                "agility" => skills.agility,
-                "thieving" => skills.thieving,
-                "slayer" => skills.slayer,
-                "farming" => skills.farming,
-                "runecrafting" => skills.runecrafting,
-                _ => 0,
-            };
+            if event.new_level >= 99 {
+                reached.push(Milestone::Level99(event.skill_name.clone()));
+            }
+
+            let total = total_level(skills);
+            for &threshold in TOTAL_LEVEL_MILESTONES.iter() {
+                if total >= threshold {
+                    reached.push(Milestone::TotalLevel(threshold));
+                }
+            }
 
-            let level = calculate_level(skill_xp, &settings.experience_curve);
-            println!("Gained {} experience in {}. New level: {}", event.experience, event.skill_name, level);
+            for milestone in reached {
+                // Only announce each milestone the first time it is reached
+                if milestones.reached.insert(milestone.clone()) {
+                    let broadcast = !matches!(milestone, Milestone::FirstLevel50);
+                    if broadcast {
+                        println!("[Broadcast] {}", milestone.description());
+                    } else {
+                        println!("Achievement unlocked: {}", milestone.description());
+                    }
+
+                    milestone_events.send(MilestoneEvent {
+                        milestone,
+                        broadcast,
+                    });
+                }
+            }
         }
     }
 }
@@ -149,87 +215,105 @@ fn handle_resource_gathering(
     mut commands: Commands,
     mut events: EventReader<ResourceGatheringEvent>,
     settings: Res<SkillsSettings>,
-    mut query: Query<(Entity, &mut Skills), With<Player>>,
+    resource_database: Res<ResourceDatabase>,
+    query: Query<(Entity, &Skills, &Inventory), With<Player>>,
     player_transform_query: Query<&Transform, With<Player>>,
     mut gathering_query: Query<(Entity, &mut GatheringInProgress)>,
-    resource_query: Query<(Entity, &Transform, &ResourceNodeType)>,
+    resource_query: Query<(Entity, &Transform, &ResourceNode), Without<DepletedResource>>,
     time: Res<Time>,
     mut skill_events: EventWriter<SkillExperienceEvent>,
     mut inventory_events: EventWriter<InventoryUpdateEvent>,
+    mut depleted_events: EventWriter<ResourceDepletedEvent>,
 ) {
+    let mut rng = rand::thread_rng();
+
     // First, process ongoing gathering
     for (entity, mut gathering) in gathering_query.iter_mut() {
-        gathering.progress += time.delta_seconds();
+        let Some(definition) = resource_database.resources.get(&gathering.resource_id) else {
+            commands.entity(entity).remove::<GatheringInProgress>();
+            continue;
+        };
+        let Ok((_, skills, inventory)) = query.get(entity) else {
+            continue;
+        };
+        let Ok(player_transform) = player_transform_query.get(entity) else {
+            continue;
+        };
+
+        // Stop if the node is gone or the player walked away from it
+        let node_in_range = resource_query.get(gathering.target_entity)
+            .map(|(_, resource_transform, _)| {
+                resource_transform.translation.distance(player_transform.translation) <= GATHERING_RANGE
+            })
+            .unwrap_or(false);
+        if !node_in_range {
+            println!("You stop gathering from the {}.", definition.name);
+            commands.entity(entity).remove::<GatheringInProgress>();
+            continue;
+        }
 
-        // Check if gathering is complete
-        if gathering.progress >= gathering.total_time {
-            // Determine which skill to give experience to
-            let skill_name = match gathering.resource_type {
-                ResourceNodeType::Tree => "woodcutting",
-                ResourceNodeType::Rock => "mining",
-                ResourceNodeType::OreDeposit => "mining",
-                ResourceNodeType::FishingSpot => "fishing",
-            };
+        // Stop once there is no room left for the product
+        if !inventory.has_room_for(definition.product_item_id) {
+            let message = "Your inventory is too full to hold any more.".to_string();
+            println!("{}", message);
+            spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+            commands.entity(entity).remove::<GatheringInProgress>();
+            continue;
+        }
 
-            // Calculate experience based on resource type
-            let base_xp = settings.gathering_base_experience;
-            let experience = match gathering.resource_type {
-                ResourceNodeType::Tree => (base_xp * 1.0) as u32,
-                ResourceNodeType::Rock => (base_xp * 1.2) as u32,
-                ResourceNodeType::OreDeposit => (base_xp * 1.5) as u32,
-                ResourceNodeType::FishingSpot => (base_xp * 1.1) as u32,
-            };
+        gathering.progress += time.delta_seconds();
 
-            // Send skill experience event
-            skill_events.send(SkillExperienceEvent {
-                skill_name: skill_name.to_string(),
-                experience,
-            });
+        // Roll for success once per gathering tick
+        if gathering.progress < gathering.total_time {
+            continue;
+        }
+        gathering.progress -= gathering.total_time;
 
-            // Create a floating text to show XP gain
-            if let Ok(player_transform) = player_transform_query.get_single() {
-                commands.spawn((
-                    Text2dBundle {
-                        text: Text::from_section(
-                            format!("+{} {} XP", experience, skill_name),
-                            TextStyle {
-                                font_size: 24.0,
-                                color: Color::rgb(0.9, 0.9, 0.1),
-                                ..default()
-                            },
-                        ).with_alignment(TextAlignment::Center),
-                        transform: Transform::from_translation(
-                            player_transform.translation + Vec3::new(0.0, 2.0, 0.0)
-                        ),
-                        ..default()
-                    },
-                    FloatingText {
-                        lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-                        velocity: Vec3::new(0.0, 1.0, 0.0),
-                        fade_start: 1.0,
-                    },
-                ));
-            }
+        let skill_level = level_for_experience(skills.experience(definition.skill_name).unwrap_or(0));
+        let Some(tool) = resource_database.best_tool(inventory, definition.tool_type, skill_level) else {
+            println!("You no longer have {} to gather with.", definition.tool_type.name());
+            commands.entity(entity).remove::<GatheringInProgress>();
+            continue;
+        };
 
-            // Add gathered resource to inventory
-            let item_id = get_item_id_for_resource(&gathering.resource_type);
-            let quantity = 1; // Basic quantity, could be randomized or based on skills
+        let success_chance = gathering_success_chance(skill_level, definition, tool);
+        if !rng.gen_bool(success_chance as f64) {
+            continue;
+        }
 
-            // Send inventory update event
-            inventory_events.send(InventoryUpdateEvent {
-                item_id,
-                quantity: quantity as i32, // Positive for adding
+        // Send skill experience event
+        skill_events.send(SkillExperienceEvent {
+            skill_name: definition.skill_name.to_string(),
+            experience: definition.experience,
+        });
+
+        // Create a floating text to show XP gain
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("+{} {} XP", definition.experience, definition.skill_name),
+            Color::rgb(0.9, 0.9, 0.1),
+        );
+
+        // Send inventory update event for the gathered product
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: definition.product_item_id,
+            quantity: 1, // Positive for adding
+        });
+
+        println!("Gathered resource: {}", definition.name);
+
+        // The node may run out after a successful gather
+        if rng.gen_bool(definition.depletion_chance as f64) {
+            commands.entity(gathering.target_entity).insert(DepletedResource {
+                respawn_timer: Timer::from_seconds(definition.respawn_time, TimerMode::Once),
             });
-
-            // Remove gathering component
-            commands.entity(entity).remove::<GatheringInProgress>();
-
-            println!("Gathered resource: {:?}", gathering.resource_type);
+            depleted_events.send(ResourceDepletedEvent { entity: gathering.target_entity });
         }
     }
 
     // Then, process new gathering events
-    if let Ok((player_entity, _)) = query.get_single_mut() {
+    if let Ok((player_entity, skills, inventory)) = query.get_single() {
         for event in events.read() {
             // Check if player is already gathering
             if gathering_query.contains(player_entity) {
@@ -237,68 +321,87 @@ fn handle_resource_gathering(
                 continue;
             }
 
-            // Start gathering
-            let gathering_time = settings.gathering_time_base;
+            // Look up the resource tier of the node
+            let Ok((_, resource_transform, resource_node)) = resource_query.get(event.entity) else {
+                println!("Could not find resource node for entity: {:?}", event.entity);
+                continue;
+            };
+            let Some(definition) = resource_database.resources.get(&resource_node.definition_id) else {
+                println!("Unknown resource definition: {}", resource_node.definition_id);
+                continue;
+            };
+
+            // Check level requirement
+            let skill_level = level_for_experience(skills.experience(definition.skill_name).unwrap_or(0));
+            if skill_level < definition.level_requirement {
+                let message = format!("You need a {} level of {} to gather from the {}.",
+                    definition.skill_name, definition.level_requirement, definition.name);
+                println!("{}", message);
+                spawn_floating_text(&mut commands, resource_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+                continue;
+            }
 
-            // Get the position of the resource being gathered
-            let mut target_position = None;
-            if let Ok((_, resource_transform, _)) = resource_query.get(event.entity) {
-                target_position = Some(resource_transform.translation);
-                println!("Resource position set: {:?}", resource_transform.translation);
-            } else {
-                println!("Could not find resource position for entity: {:?}", event.entity);
+            // Check tool requirement
+            if resource_database.best_tool(inventory, definition.tool_type, skill_level).is_none() {
+                let message = format!("You need {} to gather from the {}.", definition.tool_type.name(), definition.name);
+                println!("{}", message);
+                spawn_floating_text(&mut commands, resource_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+                continue;
             }
 
             commands.entity(player_entity).insert(GatheringInProgress {
                 resource_type: event.resource_type.clone(),
+                resource_id: definition.id,
                 target_entity: event.entity,
                 progress: 0.0,
-                total_time: gathering_time,
-                target_position,
+                total_time: settings.gathering_tick_time,
+                target_position: Some(resource_transform.translation),
             });
 
-            println!("Started gathering: {:?}", event.resource_type);
+            println!("Started gathering: {}", definition.name);
         }
     }
 }
 
-// Check for player interaction with resources
+// Start gathering when the player interacts with a resource node
 fn check_resource_interaction(
-    keyboard_input: Res<Input<KeyCode>>,
-    player_query: Query<&Transform, With<Player>>,
-    resource_query: Query<(Entity, &Transform, &ResourceNodeType)>,
+    mut interact_events: EventReader<InteractEvent>,
+    resource_query: Query<&ResourceNodeType, Without<DepletedResource>>,
     mut gathering_events: EventWriter<ResourceGatheringEvent>,
 ) {
-    // Only check when the F key is pressed
-    if keyboard_input.just_pressed(KeyCode::F) {
-        if let Ok(player_transform) = player_query.get_single() {
-            // Find the closest resource within interaction range
-            let interaction_range = 3.0; // Increased range for better usability
-            let mut closest_resource = None;
-            let mut closest_distance = f32::MAX;
-
-            for (entity, transform, resource_type) in resource_query.iter() {
-                let distance = player_transform.translation.distance(transform.translation);
-
-                if distance < interaction_range && distance < closest_distance {
-                    closest_distance = distance;
-                    closest_resource = Some((entity, resource_type.clone()));
-                }
-            }
-
-            // If a resource is in range, send a gathering event
-            if let Some((entity, resource_type)) = closest_resource {
-                gathering_events.send(ResourceGatheringEvent {
-                    resource_type,
-                    entity,
-                });
-            } else {
-                println!("No resources in range to gather.");
-            }
+    for event in interact_events.read() {
+        if let Ok(resource_type) = resource_query.get(event.target) {
+            gathering_events.send(ResourceGatheringEvent {
+                resource_type: resource_type.clone(),
+                entity: event.target,
+            });
         }
     }
 }
 
+// Spawn a floating text that drifts upward and fades out
+pub fn spawn_floating_text(commands: &mut Commands, position: Vec3, message: String, color: Color) {
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                message,
+                TextStyle {
+                    font_size: 24.0,
+                    color,
+                    ..default()
+                },
+            ).with_alignment(TextAlignment::Center),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        FloatingText {
+            lifetime: Timer::from_seconds(2.0, TimerMode::Once),
+            velocity: Vec3::new(0.0, 1.0, 0.0),
+            fade_start: 1.0,
+        },
+    ));
+}
+
 // System to update floating text effects
 fn update_floating_text(
     mut commands: Commands,
@@ -328,14 +431,10 @@ fn update_floating_text(
     }
 }
 
-// Calculate level from experience
-pub fn calculate_level(experience: u32, experience_curve: &[u32]) -> u32 {
-    for (level, &required_xp) in experience_curve.iter().enumerate() {
-        if experience < required_xp {
-            return (level + 1) as u32;
-        }
-    }
-
-    // If experience is beyond the highest level in the curve
-    experience_curve.len() as u32 + 1
+// Sum of the (non-virtual) levels of every skill
+pub fn total_level(skills: &Skills) -> u32 {
+    SKILL_NAMES.iter()
+        .filter_map(|name| skills.experience(name))
+        .map(level_for_experience)
+        .sum()
 }
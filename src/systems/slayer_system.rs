@@ -0,0 +1,367 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::shared::components::{Health, Skills};
+use crate::shared::entities::NPC;
+use crate::client::input::Player;
+use crate::systems::combat::combat_level;
+use crate::systems::combat_system::EnemyKilledEvent;
+use crate::systems::experience::level_for_experience;
+use crate::systems::interaction_system::InteractEvent;
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+pub struct SlayerPlugin;
+
+impl Plugin for SlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SlayerDatabase>()
+           .add_systems(Startup, spawn_slayer_masters)
+           .add_systems(Update, (
+               talk_to_slayer_master,
+               track_slayer_kills,
+           ));
+    }
+}
+
+// A monster that can only be fought with a high enough slayer level
+#[derive(Clone, Debug)]
+pub struct SlayerMonster {
+    pub name: String,
+    pub level_requirement: u32,
+}
+
+// A monster a master can assign, and how many of it
+#[derive(Clone, Debug)]
+pub struct SlayerAssignment {
+    pub monster: String,
+    // Lowest player combat level this assignment is given at
+    pub min_combat_level: u32,
+    pub min_count: u32,
+    pub max_count: u32,
+    pub weight: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct SlayerMasterDefinition {
+    pub name: String,
+    pub combat_level_requirement: u32,
+    pub points_per_task: u32,
+    pub position: Vec3,
+    pub assignments: Vec<SlayerAssignment>,
+}
+
+// Resource for slayer masters and slayer-only monsters (a master's ID is its index)
+#[derive(Resource)]
+pub struct SlayerDatabase {
+    pub masters: Vec<SlayerMasterDefinition>,
+    pub monsters: Vec<SlayerMonster>,
+    // Every this many completed tasks awards bonus points
+    pub bonus_task_interval: u32,
+    pub bonus_points_multiplier: u32,
+}
+
+impl Default for SlayerDatabase {
+    fn default() -> Self {
+        let masters = vec![
+            SlayerMasterDefinition {
+                name: "Turael".to_string(),
+                combat_level_requirement: 3,
+                points_per_task: 2,
+                position: Vec3::new(-4.0, 0.0, -12.0),
+                assignments: vec![
+                    assignment("Chicken", 3, 15, 30, 8),
+                    assignment("Cow", 3, 15, 30, 8),
+                    assignment("Goblin", 3, 15, 30, 8),
+                    assignment("Crawling Hand", 10, 15, 30, 6),
+                    assignment("Cave Crawler", 15, 15, 30, 5),
+                ],
+            },
+            SlayerMasterDefinition {
+                name: "Vannaka".to_string(),
+                combat_level_requirement: 40,
+                points_per_task: 8,
+                position: Vec3::new(-7.0, 0.0, -12.0),
+                assignments: vec![
+                    assignment("Hill Giant", 40, 40, 80, 7),
+                    assignment("Banshee", 40, 40, 80, 6),
+                    assignment("Rockslug", 40, 40, 80, 6),
+                    assignment("Moss Giant", 50, 40, 80, 7),
+                    assignment("Pyrefiend", 55, 40, 80, 6),
+                ],
+            },
+            SlayerMasterDefinition {
+                name: "Duradel".to_string(),
+                combat_level_requirement: 100,
+                points_per_task: 15,
+                position: Vec3::new(-10.0, 0.0, -12.0),
+                assignments: vec![
+                    assignment("Fire Giant", 100, 130, 200, 7),
+                    assignment("Black Demon", 100, 130, 200, 8),
+                    assignment("Gargoyle", 100, 130, 200, 8),
+                    assignment("Nechryael", 100, 130, 200, 9),
+                    assignment("Abyssal Demon", 100, 130, 200, 12),
+                ],
+            },
+        ];
+
+        let monsters = vec![
+            slayer_monster("Crawling Hand", 5),
+            slayer_monster("Cave Crawler", 10),
+            slayer_monster("Banshee", 15),
+            slayer_monster("Rockslug", 20),
+            slayer_monster("Pyrefiend", 30),
+            slayer_monster("Gargoyle", 75),
+            slayer_monster("Nechryael", 80),
+            slayer_monster("Abyssal Demon", 85),
+        ];
+
+        Self {
+            masters,
+            monsters,
+            bonus_task_interval: 10,
+            bonus_points_multiplier: 5,
+        }
+    }
+}
+
+// Helpers to define assignments and slayer monsters
+fn assignment(monster: &str, min_combat_level: u32, min_count: u32, max_count: u32, weight: u32) -> SlayerAssignment {
+    SlayerAssignment {
+        monster: monster.to_string(),
+        min_combat_level,
+        min_count,
+        max_count,
+        weight,
+    }
+}
+
+fn slayer_monster(name: &str, level_requirement: u32) -> SlayerMonster {
+    SlayerMonster {
+        name: name.to_string(),
+        level_requirement,
+    }
+}
+
+impl SlayerDatabase {
+    // Slayer level needed to fight a monster (1 for anything that isn't slayer-only)
+    pub fn level_requirement(&self, monster_name: &str) -> u32 {
+        self.monsters.iter()
+            .find(|monster| monster.name == monster_name)
+            .map_or(1, |monster| monster.level_requirement)
+    }
+
+    // Pick a task from a master for a player's combat and slayer levels
+    pub fn roll_task(&self, master_id: usize, combat_level: u32, slayer_level: u32, rng: &mut impl Rng) -> Option<SlayerTask> {
+        let master = self.masters.get(master_id)?;
+        let options: Vec<&SlayerAssignment> = master.assignments.iter()
+            .filter(|assignment| combat_level >= assignment.min_combat_level)
+            .filter(|assignment| slayer_level >= self.level_requirement(&assignment.monster))
+            .collect();
+
+        let total_weight: u32 = options.iter().map(|assignment| assignment.weight).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total_weight);
+        let assignment = options.into_iter().find(|assignment| {
+            if roll < assignment.weight {
+                true
+            } else {
+                roll -= assignment.weight;
+                false
+            }
+        })?;
+
+        let count = rng.gen_range(assignment.min_count..=assignment.max_count);
+        Some(SlayerTask {
+            master_id,
+            monster: assignment.monster.clone(),
+            total: count,
+            remaining: count,
+        })
+    }
+}
+
+// A slayer task: kill a number of one kind of monster
+#[derive(Clone, Debug)]
+pub struct SlayerTask {
+    pub master_id: usize,
+    pub monster: String,
+    pub total: u32,
+    pub remaining: u32,
+}
+
+// The player's current task and slayer points
+#[derive(Component, Default)]
+pub struct SlayerProgress {
+    pub task: Option<SlayerTask>,
+    pub points: u32,
+    // Tasks completed in total, for the periodic bonus points
+    pub total_tasks_completed: u32,
+}
+
+// Links a slayer master NPC to its definition
+#[derive(Component)]
+pub struct SlayerMaster {
+    pub master_id: usize,
+}
+
+// Spawn the slayer masters outside the village
+fn spawn_slayer_masters(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    database: Res<SlayerDatabase>,
+) {
+    let body_mesh = meshes.add(Mesh::from(shape::Capsule {
+        radius: 0.3,
+        depth: 0.9,
+        ..default()
+    }));
+    let head_mesh = meshes.add(Mesh::from(shape::UVSphere {
+        radius: 0.22,
+        sectors: 12,
+        stacks: 12,
+    }));
+    let robe_material = materials.add(Color::rgb(0.15, 0.15, 0.15).into());
+    let skin_material = materials.add(Color::rgb(1.0, 0.8, 0.6).into());
+
+    for (master_id, master) in database.masters.iter().enumerate() {
+        commands.spawn((
+            PbrBundle {
+                mesh: body_mesh.clone(),
+                material: robe_material.clone(),
+                transform: Transform::from_translation(master.position + Vec3::Y * 0.75),
+                ..default()
+            },
+            NPC {
+                id: 100 + master_id as u64,
+                name: master.name.clone(),
+                level: master.combat_level_requirement,
+            },
+            SlayerMaster { master_id },
+        )).with_children(|parent| {
+            parent.spawn(PbrBundle {
+                mesh: head_mesh.clone(),
+                material: skin_material.clone(),
+                transform: Transform::from_xyz(0.0, 0.85, 0.0),
+                ..default()
+            });
+        });
+    }
+}
+
+// Press F next to a slayer master to get a new task, or hear how the current one is going
+fn talk_to_slayer_master(
+    mut commands: Commands,
+    mut interact_events: EventReader<InteractEvent>,
+    database: Res<SlayerDatabase>,
+    mut player_query: Query<(&Transform, &Skills, &mut SlayerProgress), With<Player>>,
+    master_query: Query<&SlayerMaster>,
+) {
+    let Some(master_id) = interact_events.read().last()
+        .and_then(|event| master_query.get(event.target).ok())
+        .map(|master| master.master_id)
+    else {
+        return;
+    };
+    let Ok((player_transform, skills, mut progress)) = player_query.get_single_mut() else {
+        return;
+    };
+    let Some(master) = database.masters.get(master_id) else {
+        return;
+    };
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+
+    if let Some(task) = &progress.task {
+        let message = format!("{}: You're still hunting {}; you have {} to go.", master.name, task.monster, task.remaining);
+        println!("{}", message);
+        spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.9, 0.9));
+        return;
+    }
+
+    let player_combat_level = combat_level(skills);
+    if player_combat_level < master.combat_level_requirement {
+        let message = format!("{}: Come back when you have a combat level of {}.", master.name, master.combat_level_requirement);
+        println!("{}", message);
+        spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.3, 0.3));
+        return;
+    }
+
+    let slayer_level = level_for_experience(skills.slayer);
+    let Some(task) = database.roll_task(master_id, player_combat_level, slayer_level, &mut rand::thread_rng()) else {
+        println!("{}: I don't have anything for you right now.", master.name);
+        return;
+    };
+
+    let message = format!("{}: Your new task is to kill {} {}.", master.name, task.total, task.monster);
+    println!("{}", message);
+    spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.9, 0.9));
+    progress.task = Some(task);
+}
+
+// Count kills of the task's monster, granting slayer XP and points when the task is done
+fn track_slayer_kills(
+    mut commands: Commands,
+    mut events: EventReader<EnemyKilledEvent>,
+    database: Res<SlayerDatabase>,
+    mut player_query: Query<(&Transform, &mut SlayerProgress), With<Player>>,
+    npc_query: Query<(&NPC, &Health)>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+) {
+    let Ok((player_transform, mut progress)) = player_query.get_single_mut() else {
+        return;
+    };
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+
+    for event in events.read() {
+        if !event.by_player {
+            continue;
+        }
+        let Ok((npc, health)) = npc_query.get(event.enemy) else {
+            continue;
+        };
+        let Some(task) = progress.task.as_mut() else {
+            continue;
+        };
+        if task.monster != npc.name || task.remaining == 0 {
+            continue;
+        }
+
+        // Slayer XP for a kill on task is the monster's hitpoints
+        task.remaining -= 1;
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "slayer".to_string(),
+            experience: health.maximum,
+        });
+        spawn_floating_text(
+            &mut commands,
+            text_position,
+            format!("+{} slayer XP", health.maximum),
+            Color::rgb(0.9, 0.9, 0.1),
+        );
+
+        if task.remaining > 0 {
+            continue;
+        }
+
+        let master_id = task.master_id;
+        progress.task = None;
+        progress.total_tasks_completed += 1;
+
+        let base_points = database.masters.get(master_id).map_or(0, |master| master.points_per_task);
+        let points = if progress.total_tasks_completed % database.bonus_task_interval == 0 {
+            base_points * database.bonus_points_multiplier
+        } else {
+            base_points
+        };
+        progress.points += points;
+
+        let message = format!(
+            "You've completed {} tasks in total and received {} points; return to a slayer master.",
+            progress.total_tasks_completed, points,
+        );
+        println!("{}", message);
+        spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.4, 0.9, 0.6));
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::shared::components::Skills;
+use crate::client::input::Player;
+use crate::client::terrain::{WorldObject, nearest_world_object};
+use crate::systems::experience::level_for_experience;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+
+// Item ID of the hammer needed to use an anvil
+pub const HAMMER_ITEM_ID: u64 = 32;
+
+pub struct SmithingPlugin;
+
+impl Plugin for SmithingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SmithingDatabase>()
+           .add_event::<SmeltBarEvent>()
+           .add_event::<SmithItemEvent>()
+           .add_systems(Update, (
+               handle_smelting,
+               handle_smithing,
+           ));
+    }
+}
+
+// Request to smelt a bar at a nearby furnace
+#[derive(Event)]
+pub struct SmeltBarEvent {
+    pub bar_item_id: u64,
+}
+
+// Request to forge an item at a nearby anvil
+#[derive(Event)]
+pub struct SmithItemEvent {
+    pub product_item_id: u64,
+}
+
+// Ores smelted into a bar at a furnace
+#[derive(Clone, Debug)]
+pub struct SmeltingRecipe {
+    pub bar_item_id: u64,
+    pub ores: Vec<(u64, u32)>, // (item_id, quantity)
+    pub level_requirement: u32,
+    pub experience: u32,
+    // Some ores (iron) are impure and can fail to become a bar
+    pub success_chance: f32,
+}
+
+// Bars forged into equipment at an anvil
+#[derive(Clone, Debug)]
+pub struct SmithingRecipe {
+    pub product_item_id: u64,
+    pub bar_item_id: u64,
+    pub bars: u32,
+    pub level_requirement: u32,
+    pub experience: u32,
+}
+
+// Resource for smelting and smithing recipes (kept in display order)
+#[derive(Resource)]
+pub struct SmithingDatabase {
+    pub smelting: Vec<SmeltingRecipe>,
+    pub smithing: Vec<SmithingRecipe>,
+}
+
+impl Default for SmithingDatabase {
+    fn default() -> Self {
+        let smelting = vec![
+            SmeltingRecipe { bar_item_id: 50, ores: vec![(3, 1), (13, 1)], level_requirement: 1, experience: 6, success_chance: 1.0 },
+            SmeltingRecipe { bar_item_id: 51, ores: vec![(14, 1)], level_requirement: 15, experience: 12, success_chance: 0.5 },
+            SmeltingRecipe { bar_item_id: 52, ores: vec![(14, 1), (15, 2)], level_requirement: 30, experience: 17, success_chance: 1.0 },
+            SmeltingRecipe { bar_item_id: 53, ores: vec![(16, 1), (15, 4)], level_requirement: 50, experience: 30, success_chance: 1.0 },
+        ];
+
+        // Each metal has a dagger, sword and platebody, offset from the metal's base level
+        let mut smithing = Vec::new();
+        let metals = [
+            (50, 1, 12, 60),  // Bronze: bar, base level, XP per bar, first product ID
+            (51, 15, 25, 63), // Iron
+            (52, 30, 37, 66), // Steel
+            (53, 50, 50, 69), // Mithril
+        ];
+        for (bar_item_id, base_level, experience_per_bar, first_product_id) in metals {
+            let products = [(0, 1, 0), (1, 1, 4), (2, 5, 18)]; // (offset, bars, extra levels)
+            for (offset, bars, extra_levels) in products {
+                smithing.push(SmithingRecipe {
+                    product_item_id: first_product_id + offset,
+                    bar_item_id,
+                    bars,
+                    level_requirement: base_level + extra_levels,
+                    experience: experience_per_bar * bars,
+                });
+            }
+        }
+
+        Self { smelting, smithing }
+    }
+}
+
+// Whether the inventory holds at least a quantity of an item
+fn has_items(inventory: &Inventory, item_id: u64, quantity: u32) -> bool {
+    inventory.items.get(&item_id).copied().unwrap_or(0) >= quantity
+}
+
+// Whether the player has the ores for a smelting recipe
+pub fn can_smelt(inventory: &Inventory, recipe: &SmeltingRecipe) -> bool {
+    recipe.ores.iter().all(|&(item_id, quantity)| has_items(inventory, item_id, quantity))
+}
+
+// Whether the player has the bars and a hammer for a smithing recipe
+pub fn can_smith(inventory: &Inventory, recipe: &SmithingRecipe) -> bool {
+    has_items(inventory, HAMMER_ITEM_ID, 1) && has_items(inventory, recipe.bar_item_id, recipe.bars)
+}
+
+// Smelt ores into bars at a furnace
+fn handle_smelting(
+    mut commands: Commands,
+    mut events: EventReader<SmeltBarEvent>,
+    database: Res<SmithingDatabase>,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+    let mut rng = rand::thread_rng();
+
+    for event in events.read() {
+        let Some(recipe) = database.smelting.iter().find(|recipe| recipe.bar_item_id == event.bar_item_id) else {
+            continue;
+        };
+
+        if nearest_world_object(player_transform.translation, object_query.iter(), |object| *object == WorldObject::Furnace).is_none() {
+            println!("You need to be next to a furnace to smelt ore.");
+            continue;
+        }
+
+        if level_for_experience(skills.smithing) < recipe.level_requirement {
+            let message = format!("You need a smithing level of {} to smelt this.", recipe.level_requirement);
+            println!("{}", message);
+            spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+            continue;
+        }
+
+        if !can_smelt(inventory, recipe) {
+            println!("You don't have the ores to smelt this bar.");
+            continue;
+        }
+
+        // The ores are used up whether or not the bar comes out
+        for &(item_id, quantity) in recipe.ores.iter() {
+            inventory_events.send(InventoryUpdateEvent {
+                item_id,
+                quantity: -(quantity as i32),
+            });
+        }
+
+        if !rng.gen_bool(recipe.success_chance as f64) {
+            println!("The ore is too impure and you fail to refine it.");
+            continue;
+        }
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: recipe.bar_item_id,
+            quantity: 1,
+        });
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "smithing".to_string(),
+            experience: recipe.experience,
+        });
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("+{} smithing XP", recipe.experience),
+            Color::rgb(0.9, 0.9, 0.1),
+        );
+    }
+}
+
+// Forge bars into equipment at an anvil
+fn handle_smithing(
+    mut commands: Commands,
+    mut events: EventReader<SmithItemEvent>,
+    database: Res<SmithingDatabase>,
+    player_query: Query<(&Transform, &Skills, &Inventory), With<Player>>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+) {
+    let Ok((player_transform, skills, inventory)) = player_query.get_single() else {
+        return;
+    };
+
+    for event in events.read() {
+        let Some(recipe) = database.smithing.iter().find(|recipe| recipe.product_item_id == event.product_item_id) else {
+            continue;
+        };
+
+        if nearest_world_object(player_transform.translation, object_query.iter(), |object| *object == WorldObject::Anvil).is_none() {
+            println!("You need to be next to an anvil to smith.");
+            continue;
+        }
+
+        if level_for_experience(skills.smithing) < recipe.level_requirement {
+            let message = format!("You need a smithing level of {} to make this.", recipe.level_requirement);
+            println!("{}", message);
+            spawn_floating_text(&mut commands, player_transform.translation + Vec3::new(0.0, 2.0, 0.0), message, Color::rgb(0.9, 0.3, 0.3));
+            continue;
+        }
+
+        if !has_items(inventory, HAMMER_ITEM_ID, 1) {
+            println!("You need a hammer to work the metal.");
+            continue;
+        }
+        if !has_items(inventory, recipe.bar_item_id, recipe.bars) {
+            println!("You don't have enough bars to make this.");
+            continue;
+        }
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: recipe.bar_item_id,
+            quantity: -(recipe.bars as i32),
+        });
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: recipe.product_item_id,
+            quantity: 1,
+        });
+        skill_events.send(SkillExperienceEvent {
+            skill_name: "smithing".to_string(),
+            experience: recipe.experience,
+        });
+        spawn_floating_text(
+            &mut commands,
+            player_transform.translation + Vec3::new(0.0, 2.0, 0.0),
+            format!("+{} smithing XP", recipe.experience),
+            Color::rgb(0.9, 0.9, 0.1),
+        );
+    }
+}
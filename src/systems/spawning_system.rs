@@ -0,0 +1,339 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::shared::components::Health;
+use crate::shared::entities::NPC;
+use crate::client::physics::Velocity;
+use crate::systems::combat_system::{CombatState, Enemy};
+use crate::systems::npc_ai_system::NpcAi;
+
+pub struct SpawningPlugin;
+
+impl Plugin for SpawningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnDatabase>()
+           .init_resource::<SpawnState>()
+           .add_systems(Startup, spawn_initial_npcs)
+           .add_systems(Update, respawn_npcs);
+    }
+}
+
+// How an NPC looks
+#[derive(Clone, Debug)]
+pub enum NpcModel {
+    // Capsule body and a head, dressed in the given colour
+    Humanoid { clothes: Color, scale: f32 },
+    // Box body and a head, for animals and monsters
+    Creature { color: Color, size: f32 },
+}
+
+// Stats shared by every NPC of one type
+#[derive(Clone, Debug)]
+pub struct NpcDefinition {
+    pub hitpoints: u32,
+    pub aggression_range: f32,
+    pub attack_range: f32,
+    // Fraction of health below which the NPC runs away (0 never flees)
+    pub flee_health: f32,
+    pub model: NpcModel,
+}
+
+// One place an NPC spawns, and respawns after it dies
+#[derive(Clone, Debug)]
+pub struct SpawnPoint {
+    pub npc: String,
+    pub level: u32,
+    pub position: Vec3,
+    pub wander_radius: f32,
+    pub respawn_time: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpawnRegion {
+    pub name: String,
+    pub spawns: Vec<SpawnPoint>,
+}
+
+// Resource for NPC types (keyed by name) and the spawn points of each region
+#[derive(Resource)]
+pub struct SpawnDatabase {
+    pub npcs: HashMap<String, NpcDefinition>,
+    pub regions: Vec<SpawnRegion>,
+}
+
+impl Default for SpawnDatabase {
+    fn default() -> Self {
+        let mut npcs = HashMap::new();
+
+        // Villagers only fight back when attacked (or, for guards, when they see a theft), and run off when losing
+        insert_npc(&mut npcs, "Man", 7, 0.0, 1.5, 0.25, NpcModel::Humanoid { clothes: Color::rgb(0.6, 0.2, 0.2), scale: 1.0 });
+        insert_npc(&mut npcs, "Farmer", 12, 0.0, 1.5, 0.25, NpcModel::Humanoid { clothes: Color::rgb(0.55, 0.4, 0.2), scale: 1.0 });
+        insert_npc(&mut npcs, "Guard", 22, 0.0, 1.5, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.5, 0.5, 0.55), scale: 1.0 });
+
+        // Farm animals and goblins
+        insert_npc(&mut npcs, "Chicken", 3, 0.0, 1.0, 0.5, NpcModel::Creature { color: Color::rgb(0.95, 0.95, 0.9), size: 0.35 });
+        insert_npc(&mut npcs, "Cow", 8, 0.0, 1.8, 0.5, NpcModel::Creature { color: Color::rgb(0.45, 0.3, 0.2), size: 0.9 });
+        insert_npc(&mut npcs, "Goblin", 5, 4.0, 1.5, 0.2, NpcModel::Humanoid { clothes: Color::rgb(0.3, 0.55, 0.2), scale: 0.8 });
+
+        // Giants
+        insert_npc(&mut npcs, "Hill Giant", 35, 6.0, 2.5, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.5, 0.4, 0.3), scale: 1.8 });
+        insert_npc(&mut npcs, "Moss Giant", 60, 6.0, 2.5, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.25, 0.45, 0.2), scale: 1.9 });
+        insert_npc(&mut npcs, "Fire Giant", 111, 6.0, 2.5, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.85, 0.35, 0.1), scale: 2.0 });
+
+        // Slayer monsters
+        insert_npc(&mut npcs, "Crawling Hand", 16, 4.0, 1.2, 0.0, NpcModel::Creature { color: Color::rgb(0.8, 0.65, 0.55), size: 0.3 });
+        insert_npc(&mut npcs, "Cave Crawler", 22, 4.0, 1.5, 0.0, NpcModel::Creature { color: Color::rgb(0.4, 0.5, 0.25), size: 0.6 });
+        insert_npc(&mut npcs, "Banshee", 22, 5.0, 1.5, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.85, 0.85, 0.95), scale: 1.0 });
+        insert_npc(&mut npcs, "Rockslug", 27, 4.0, 1.5, 0.0, NpcModel::Creature { color: Color::rgb(0.45, 0.45, 0.4), size: 0.6 });
+        insert_npc(&mut npcs, "Pyrefiend", 45, 5.0, 1.5, 0.0, NpcModel::Creature { color: Color::rgb(1.0, 0.45, 0.1), size: 0.7 });
+        insert_npc(&mut npcs, "Gargoyle", 105, 6.0, 2.0, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.35, 0.35, 0.4), scale: 1.5 });
+        insert_npc(&mut npcs, "Nechryael", 105, 6.0, 2.0, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.45, 0.15, 0.2), scale: 1.6 });
+        insert_npc(&mut npcs, "Black Demon", 157, 6.0, 2.5, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.1, 0.1, 0.1), scale: 2.0 });
+        insert_npc(&mut npcs, "Abyssal Demon", 150, 6.0, 2.0, 0.0, NpcModel::Humanoid { clothes: Color::rgb(0.4, 0.1, 0.35), scale: 1.7 });
+
+        let regions = vec![
+            region("Village", &[
+                spawn("Man", 2, (3.0, 8.0), 2.0, 30.0),
+                spawn("Man", 2, (-3.0, 9.0), 2.0, 30.0),
+                spawn("Farmer", 7, (8.0, 10.0), 2.0, 30.0),
+                spawn("Guard", 21, (12.0, 10.0), 1.0, 60.0),
+                spawn("Guard", 21, (0.0, 13.0), 1.0, 60.0),
+            ]),
+            region("Farm fields", &[
+                spawn("Chicken", 1, (20.0, 5.0), 3.0, 20.0),
+                spawn("Chicken", 1, (22.0, 7.0), 3.0, 20.0),
+                spawn("Chicken", 1, (21.0, 3.0), 3.0, 20.0),
+                spawn("Cow", 2, (24.0, 12.0), 4.0, 20.0),
+                spawn("Cow", 2, (27.0, 10.0), 4.0, 20.0),
+                spawn("Cow", 2, (26.0, 15.0), 4.0, 20.0),
+            ]),
+            region("Goblin camp", &[
+                spawn("Goblin", 2, (25.0, -20.0), 4.0, 25.0),
+                spawn("Goblin", 2, (28.0, -22.0), 4.0, 25.0),
+                spawn("Goblin", 5, (23.0, -24.0), 4.0, 25.0),
+            ]),
+            region("Slayer caves", &[
+                spawn("Crawling Hand", 8, (-35.0, -20.0), 3.0, 30.0),
+                spawn("Crawling Hand", 8, (-37.0, -22.0), 3.0, 30.0),
+                spawn("Cave Crawler", 23, (-38.0, -28.0), 3.0, 30.0),
+                spawn("Cave Crawler", 23, (-34.0, -30.0), 3.0, 30.0),
+                spawn("Banshee", 23, (-40.0, -35.0), 3.0, 30.0),
+                spawn("Rockslug", 29, (-44.0, -30.0), 3.0, 30.0),
+                spawn("Pyrefiend", 43, (-44.0, -38.0), 3.0, 30.0),
+            ]),
+            region("Giants' plateau", &[
+                spawn("Hill Giant", 28, (35.0, 30.0), 5.0, 45.0),
+                spawn("Hill Giant", 28, (38.0, 34.0), 5.0, 45.0),
+                spawn("Moss Giant", 42, (40.0, 40.0), 5.0, 45.0),
+                spawn("Fire Giant", 86, (44.0, 44.0), 5.0, 60.0),
+            ]),
+            region("Demon pits", &[
+                spawn("Gargoyle", 111, (10.0, -42.0), 4.0, 60.0),
+                spawn("Nechryael", 115, (4.0, -44.0), 4.0, 60.0),
+                spawn("Black Demon", 172, (-4.0, -44.0), 4.0, 90.0),
+                spawn("Abyssal Demon", 124, (-10.0, -42.0), 4.0, 60.0),
+            ]),
+        ];
+
+        Self { npcs, regions }
+    }
+}
+
+// Helpers to define NPC types, regions and spawn points
+fn insert_npc(npcs: &mut HashMap<String, NpcDefinition>, name: &str, hitpoints: u32, aggression_range: f32, attack_range: f32, flee_health: f32, model: NpcModel) {
+    npcs.insert(name.to_string(), NpcDefinition {
+        hitpoints,
+        aggression_range,
+        attack_range,
+        flee_health,
+        model,
+    });
+}
+
+fn region(name: &str, spawns: &[SpawnPoint]) -> SpawnRegion {
+    SpawnRegion {
+        name: name.to_string(),
+        spawns: spawns.to_vec(),
+    }
+}
+
+fn spawn(npc: &str, level: u32, (x, z): (f32, f32), wander_radius: f32, respawn_time: f32) -> SpawnPoint {
+    SpawnPoint {
+        npc: npc.to_string(),
+        level,
+        position: Vec3::new(x, 0.0, z),
+        wander_radius,
+        respawn_time,
+    }
+}
+
+// An NPC created from a spawn point
+#[derive(Component, Debug, Clone)]
+pub struct SpawnedNpc {
+    pub region_id: usize,
+    pub spawn_id: usize,
+    pub home: Vec3,
+    pub wander_radius: f32,
+}
+
+// The NPC currently alive at a spawn point, or the countdown until it respawns
+pub struct SpawnSlot {
+    pub region_id: usize,
+    pub spawn_id: usize,
+    pub entity: Option<Entity>,
+    pub respawn_timer: Timer,
+}
+
+#[derive(Resource, Default)]
+pub struct SpawnState {
+    pub slots: Vec<SpawnSlot>,
+    next_npc_id: u64,
+}
+
+// Spawn one NPC at its spawn point
+#[allow(clippy::too_many_arguments)]
+fn spawn_npc(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    npc_id: u64,
+    region_id: usize,
+    spawn_id: usize,
+    spawn: &SpawnPoint,
+    definition: &NpcDefinition,
+) -> Entity {
+    let skin_material = materials.add(Color::rgb(1.0, 0.8, 0.6).into());
+    let (body_mesh, body_material, body_height, head_mesh, head_material, head_offset) = match definition.model {
+        NpcModel::Humanoid { clothes, scale } => (
+            meshes.add(Mesh::from(shape::Capsule { radius: 0.3 * scale, depth: 0.9 * scale, ..default() })),
+            materials.add(clothes.into()),
+            0.75 * scale,
+            meshes.add(Mesh::from(shape::UVSphere { radius: 0.22 * scale, sectors: 12, stacks: 12 })),
+            skin_material,
+            Vec3::new(0.0, 0.85 * scale, 0.0),
+        ),
+        NpcModel::Creature { color, size } => {
+            let material = materials.add(color.into());
+            (
+                meshes.add(Mesh::from(shape::Box::new(size * 1.6, size, size))),
+                material.clone(),
+                size / 2.0,
+                meshes.add(Mesh::from(shape::UVSphere { radius: size * 0.35, sectors: 12, stacks: 12 })),
+                material,
+                Vec3::new(size * 0.9, size * 0.4, 0.0),
+            )
+        },
+    };
+
+    commands.spawn((
+        PbrBundle {
+            mesh: body_mesh,
+            material: body_material,
+            transform: Transform::from_translation(spawn.position + Vec3::Y * body_height),
+            ..default()
+        },
+        NPC {
+            id: npc_id,
+            name: spawn.npc.clone(),
+            level: spawn.level,
+        },
+        Enemy {
+            level: spawn.level,
+            aggression_range: definition.aggression_range,
+            attack_range: definition.attack_range,
+        },
+        Health {
+            current: definition.hitpoints,
+            maximum: definition.hitpoints,
+        },
+        CombatState::default(),
+        Velocity { linear: Vec3::ZERO, angular: 0.0 },
+        NpcAi::new(definition.flee_health),
+        SpawnedNpc {
+            region_id,
+            spawn_id,
+            home: spawn.position,
+            wander_radius: spawn.wander_radius,
+        },
+    )).with_children(|parent| {
+        parent.spawn(PbrBundle {
+            mesh: head_mesh,
+            material: head_material,
+            transform: Transform::from_translation(head_offset),
+            ..default()
+        });
+    }).id()
+}
+
+// Fill every spawn point when the world is created
+fn spawn_initial_npcs(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    database: Res<SpawnDatabase>,
+    mut state: ResMut<SpawnState>,
+) {
+    state.next_npc_id = 1;
+
+    for (region_id, region) in database.regions.iter().enumerate() {
+        for (spawn_id, spawn) in region.spawns.iter().enumerate() {
+            let Some(definition) = database.npcs.get(&spawn.npc) else {
+                println!("Warning: Spawn point in {} uses unknown NPC: {}", region.name, spawn.npc);
+                continue;
+            };
+
+            let npc_id = state.next_npc_id;
+            state.next_npc_id += 1;
+            let entity = spawn_npc(&mut commands, &mut meshes, &mut materials, npc_id, region_id, spawn_id, spawn, definition);
+
+            state.slots.push(SpawnSlot {
+                region_id,
+                spawn_id,
+                entity: Some(entity),
+                respawn_timer: Timer::from_seconds(spawn.respawn_time, TimerMode::Once),
+            });
+        }
+    }
+}
+
+// Count down empty spawn points and bring their NPC back
+fn respawn_npcs(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    database: Res<SpawnDatabase>,
+    mut state: ResMut<SpawnState>,
+    npc_query: Query<(), With<SpawnedNpc>>,
+    time: Res<Time>,
+) {
+    let mut next_npc_id = state.next_npc_id;
+
+    for slot in state.slots.iter_mut() {
+        match slot.entity {
+            // Still alive
+            Some(entity) if npc_query.contains(entity) => continue,
+            // Just removed, so start the countdown
+            Some(_) => {
+                slot.entity = None;
+                slot.respawn_timer.reset();
+            },
+            None => {},
+        }
+
+        slot.respawn_timer.tick(time.delta());
+        if !slot.respawn_timer.finished() {
+            continue;
+        }
+
+        let Some(spawn) = database.regions.get(slot.region_id).and_then(|region| region.spawns.get(slot.spawn_id)) else {
+            continue;
+        };
+        let Some(definition) = database.npcs.get(&spawn.npc) else {
+            continue;
+        };
+
+        slot.entity = Some(spawn_npc(&mut commands, &mut meshes, &mut materials, next_npc_id, slot.region_id, slot.spawn_id, spawn, definition));
+        next_npc_id += 1;
+    }
+
+    state.next_npc_id = next_npc_id;
+}
@@ -0,0 +1,556 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::shared::components::Skills;
+use crate::shared::entities::NPC;
+use crate::client::input::Player;
+use crate::client::terrain::{WorldObject, WORLD_OBJECT_RANGE, nearest_world_object};
+use crate::systems::experience::level_for_experience;
+use crate::systems::inventory_system::{Inventory, InventoryUpdateEvent};
+use crate::systems::skills_system::{SkillExperienceEvent, spawn_floating_text};
+use crate::systems::combat_system::{CombatState, DamageEvent, Enemy};
+
+pub struct ThievingPlugin;
+
+impl Plugin for ThievingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ThievingDatabase>()
+           .add_event::<TheftEvent>()
+           .add_systems(Startup, spawn_market)
+           .add_systems(Update, (
+               assign_guards,
+               handle_thieving_input,
+               alert_guards.after(handle_thieving_input),
+               calm_guards,
+               recover_from_stun,
+               restock_stalls,
+           ));
+    }
+}
+
+// Sent when the player steals from a stall or is caught pickpocketing, so nearby guards can react
+#[derive(Event)]
+pub struct TheftEvent {
+    pub position: Vec3,
+}
+
+// The player has been caught and can't move or steal until the timer runs out
+#[derive(Component)]
+pub struct Stunned {
+    pub timer: Timer,
+}
+
+// Guards watch for theft and attack anyone they catch
+#[derive(Component)]
+pub struct Guard {
+    pub sight_range: f32,
+    pub chase_range: f32,
+}
+
+// A guard that has seen a theft stays aggressive until this runs out
+#[derive(Component)]
+pub struct AlertedGuard {
+    pub timer: Timer,
+}
+
+// A market stall in the world, with the goods shown on its counter
+#[derive(Component)]
+pub struct MarketStall {
+    pub definition_id: u32,
+    pub goods: Entity,
+}
+
+// A stall that has been stolen from and has nothing left until it restocks
+#[derive(Component)]
+pub struct EmptyStall {
+    pub timer: Timer,
+}
+
+// A possible reward from a theft (item_id, quantity, weight)
+#[derive(Clone, Debug)]
+pub struct LootEntry {
+    pub item_id: u64,
+    pub quantity: u32,
+    pub weight: u32,
+}
+
+// Pick one weighted entry from a loot table
+pub fn roll_loot<'a>(table: &'a [LootEntry], rng: &mut impl Rng) -> Option<&'a LootEntry> {
+    let total_weight: u32 = table.iter().map(|entry| entry.weight).sum();
+    if total_weight == 0 {
+        return None;
+    }
+
+    let mut roll = rng.gen_range(0..total_weight);
+    for entry in table {
+        if roll < entry.weight {
+            return Some(entry);
+        }
+        roll -= entry.weight;
+    }
+
+    None
+}
+
+// An NPC that can be pickpocketed
+#[derive(Clone, Debug)]
+pub struct PickpocketDefinition {
+    pub level_requirement: u32,
+    pub experience: u32,
+    // Success chance at the level requirement and at level 99
+    pub success_low: f32,
+    pub success_high: f32,
+    pub gold: u32,
+    pub loot: Vec<LootEntry>,
+    // Damage and stun dealt when the NPC catches the player
+    pub failure_damage: u32,
+    pub stun_time: f32,
+}
+
+// A market stall that can be stolen from
+#[derive(Clone, Debug)]
+pub struct StallDefinition {
+    pub name: String,
+    pub level_requirement: u32,
+    pub experience: u32,
+    // Success chance at the level requirement and at level 99
+    pub success_low: f32,
+    pub success_high: f32,
+    pub loot: Vec<LootEntry>,
+    pub restock_time: f32,
+    // Damage and stun dealt when the stall owner catches the player
+    pub failure_damage: u32,
+    pub stun_time: f32,
+    pub color: Color,
+}
+
+// Resource for thieving targets (pickpockets are keyed by NPC name)
+#[derive(Resource)]
+pub struct ThievingDatabase {
+    pub pickpockets: HashMap<String, PickpocketDefinition>,
+    pub stalls: HashMap<u32, StallDefinition>,
+}
+
+impl Default for ThievingDatabase {
+    fn default() -> Self {
+        let mut pickpockets = HashMap::new();
+        pickpockets.insert("Man".to_string(), PickpocketDefinition {
+            level_requirement: 1,
+            experience: 8,
+            success_low: 0.7,
+            success_high: 0.95,
+            gold: 3,
+            loot: Vec::new(),
+            failure_damage: 1,
+            stun_time: 5.0,
+        });
+        pickpockets.insert("Farmer".to_string(), PickpocketDefinition {
+            level_requirement: 10,
+            experience: 14,
+            success_low: 0.6,
+            success_high: 0.95,
+            gold: 9,
+            loot: vec![loot(160, 1, 4), loot(161, 1, 2), loot(163, 1, 1), loot(0, 0, 4)],
+            failure_damage: 1,
+            stun_time: 5.0,
+        });
+        pickpockets.insert("Guard".to_string(), PickpocketDefinition {
+            level_requirement: 40,
+            experience: 46,
+            success_low: 0.5,
+            success_high: 0.9,
+            gold: 30,
+            loot: Vec::new(),
+            failure_damage: 2,
+            stun_time: 5.0,
+        });
+
+        let mut stalls = HashMap::new();
+        stalls.insert(1, StallDefinition {
+            name: "Herb stall".to_string(),
+            level_requirement: 5,
+            experience: 10,
+            success_low: 0.7,
+            success_high: 0.98,
+            loot: vec![loot(110, 1, 4), loot(111, 1, 2), loot(120, 1, 3), loot(115, 1, 3)],
+            restock_time: 5.0,
+            failure_damage: 1,
+            stun_time: 4.0,
+            color: Color::rgb(0.3, 0.6, 0.2),
+        });
+        stalls.insert(2, StallDefinition {
+            name: "Silk stall".to_string(),
+            level_requirement: 20,
+            experience: 24,
+            success_low: 0.65,
+            success_high: 0.95,
+            loot: vec![loot(150, 1, 1)],
+            restock_time: 8.0,
+            failure_damage: 2,
+            stun_time: 5.0,
+            color: Color::rgb(0.85, 0.85, 0.95),
+        });
+        stalls.insert(3, StallDefinition {
+            name: "Gem stall".to_string(),
+            level_requirement: 75,
+            experience: 160,
+            success_low: 0.55,
+            success_high: 0.9,
+            loot: vec![loot(84, 1, 10), loot(85, 1, 4), loot(86, 1, 1)],
+            restock_time: 180.0,
+            failure_damage: 4,
+            stun_time: 6.0,
+            color: Color::rgb(0.3, 0.3, 0.8),
+        });
+
+        Self { pickpockets, stalls }
+    }
+}
+
+// Helper to build a loot table entry (item 0 means nothing beyond the gold)
+fn loot(item_id: u64, quantity: u32, weight: u32) -> LootEntry {
+    LootEntry { item_id, quantity, weight }
+}
+
+// Chance a theft succeeds, interpolated between the requirement and level 99
+pub fn theft_success_chance(thieving_level: u32, level_requirement: u32, success_low: f32, success_high: f32) -> f32 {
+    let span = (99 - level_requirement.min(98)) as f32;
+    let progress = (thieving_level.saturating_sub(level_requirement) as f32 / span).min(1.0);
+    success_low + (success_high - success_low) * progress
+}
+
+// Market stalls along the village square (definition_id, position)
+const MARKET_STALLS: [(u32, Vec3); 3] = [
+    (1, Vec3::new(4.0, 0.0, 12.0)),
+    (2, Vec3::new(8.0, 0.0, 14.0)),
+    (3, Vec3::new(12.0, 0.0, 12.5)),
+];
+
+// Spawn the market stalls
+fn spawn_market(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    database: Res<ThievingDatabase>,
+) {
+    let counter_mesh = meshes.add(Mesh::from(shape::Box::new(2.0, 0.9, 1.0)));
+    let goods_mesh = meshes.add(Mesh::from(shape::Box::new(1.6, 0.2, 0.7)));
+    let counter_material = materials.add(Color::rgb(0.5, 0.35, 0.2).into());
+
+    for (definition_id, position) in MARKET_STALLS {
+        let Some(definition) = database.stalls.get(&definition_id) else {
+            continue;
+        };
+
+        // Goods on the counter are hidden while the stall is empty
+        let goods = commands.spawn(PbrBundle {
+            mesh: goods_mesh.clone(),
+            material: materials.add(definition.color.into()),
+            transform: Transform::from_xyz(0.0, 0.55, 0.0),
+            ..default()
+        }).id();
+
+        commands.spawn((
+            PbrBundle {
+                mesh: counter_mesh.clone(),
+                material: counter_material.clone(),
+                transform: Transform::from_translation(position + Vec3::Y * 0.45),
+                ..default()
+            },
+            WorldObject::Stall,
+            MarketStall { definition_id, goods },
+        )).add_child(goods);
+    }
+}
+
+// Village guards (spawned from the village spawn points) watch the market for thieves
+fn assign_guards(
+    mut commands: Commands,
+    npc_query: Query<(Entity, &NPC), Added<NPC>>,
+) {
+    for (entity, npc) in npc_query.iter() {
+        if npc.name == "Guard" {
+            commands.entity(entity).insert(Guard {
+                sight_range: 8.0,
+                chase_range: 12.0,
+            });
+        }
+    }
+}
+
+// Press T to pickpocket the nearest NPC or steal from the nearest stall
+#[allow(clippy::too_many_arguments)]
+fn handle_thieving_input(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    database: Res<ThievingDatabase>,
+    mut player_query: Query<(Entity, &Transform, &Skills, &mut Inventory), (With<Player>, Without<Stunned>)>,
+    npc_query: Query<(Entity, &Transform, &NPC)>,
+    object_query: Query<(Entity, &Transform, &WorldObject)>,
+    stall_query: Query<(&MarketStall, Option<&EmptyStall>)>,
+    mut goods_query: Query<&mut Visibility>,
+    mut skill_events: EventWriter<SkillExperienceEvent>,
+    mut inventory_events: EventWriter<InventoryUpdateEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut theft_events: EventWriter<TheftEvent>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::T) {
+        return;
+    }
+    let Ok((player_entity, player_transform, skills, mut inventory)) = player_query.get_single_mut() else {
+        return;
+    };
+    let thieving_level = level_for_experience(skills.thieving);
+    let text_position = player_transform.translation + Vec3::new(0.0, 2.0, 0.0);
+    let mut rng = rand::thread_rng();
+
+    // Stalls take priority when standing at one
+    let stall = nearest_world_object(
+        player_transform.translation,
+        object_query.iter(),
+        |object| *object == WorldObject::Stall,
+    ).and_then(|(entity, _)| stall_query.get(entity).ok().map(|stall| (entity, stall)));
+
+    if let Some((stall_entity, (stall, empty))) = stall {
+        let Some(definition) = database.stalls.get(&stall.definition_id) else {
+            return;
+        };
+        if empty.is_some() {
+            let message = format!("The {} is empty.", definition.name.to_lowercase());
+            println!("{}", message);
+            spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.8, 0.8, 0.8));
+            return;
+        }
+        if thieving_level < definition.level_requirement {
+            let message = format!("You need a thieving level of {} to steal from this stall.", definition.level_requirement);
+            println!("{}", message);
+            spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.3, 0.3));
+            return;
+        }
+
+        let Some(entry) = roll_loot(&definition.loot, &mut rng) else {
+            return;
+        };
+        if !inventory.has_room_for(entry.item_id) {
+            println!("You don't have enough inventory space.");
+            return;
+        }
+
+        let success_chance = theft_success_chance(thieving_level, definition.level_requirement, definition.success_low, definition.success_high);
+        if !rng.gen_bool(success_chance as f64) {
+            let caught = Caught {
+                thief: player_entity,
+                thief_position: player_transform.translation,
+                catcher_position: object_query.get(stall_entity).map_or(player_transform.translation, |(_, transform, _)| transform.translation),
+                shout: "Hey! Get your hands off there!",
+                damage: definition.failure_damage,
+                stun_time: definition.stun_time,
+            };
+            get_caught(&mut commands, caught, &mut damage_events, &mut theft_events);
+            println!("You fail to steal from the {} and are stunned.", definition.name.to_lowercase());
+            return;
+        }
+
+        inventory_events.send(InventoryUpdateEvent {
+            item_id: entry.item_id,
+            quantity: entry.quantity as i32,
+        });
+        award_thieving_experience(&mut commands, &mut skill_events, text_position, definition.experience);
+
+        // The stall is empty until it restocks
+        commands.entity(stall_entity).insert(EmptyStall {
+            timer: Timer::from_seconds(definition.restock_time, TimerMode::Once),
+        });
+        if let Ok(mut visibility) = goods_query.get_mut(stall.goods) {
+            *visibility = Visibility::Hidden;
+        }
+
+        // Anyone watching a stall theft will know what happened
+        theft_events.send(TheftEvent {
+            position: player_transform.translation,
+        });
+        println!("You steal from the {}.", definition.name.to_lowercase());
+        return;
+    }
+
+    // Otherwise pickpocket the closest NPC within reach
+    let target = npc_query.iter()
+        .filter(|(_, transform, _)| transform.translation.distance(player_transform.translation) <= WORLD_OBJECT_RANGE)
+        .min_by(|(_, a, _), (_, b, _)| {
+            a.translation.distance(player_transform.translation)
+                .total_cmp(&b.translation.distance(player_transform.translation))
+        });
+    let Some((_, npc_transform, npc)) = target else {
+        println!("There's nothing to steal from here.");
+        return;
+    };
+    let Some(definition) = database.pickpockets.get(&npc.name) else {
+        println!("You can't pickpocket the {}.", npc.name.to_lowercase());
+        return;
+    };
+
+    if thieving_level < definition.level_requirement {
+        let message = format!("You need a thieving level of {} to pickpocket the {}.", definition.level_requirement, npc.name.to_lowercase());
+        println!("{}", message);
+        spawn_floating_text(&mut commands, text_position, message, Color::rgb(0.9, 0.3, 0.3));
+        return;
+    }
+
+    let success_chance = theft_success_chance(thieving_level, definition.level_requirement, definition.success_low, definition.success_high);
+    if rng.gen_bool(success_chance as f64) {
+        inventory.gold += definition.gold;
+        if let Some(entry) = roll_loot(&definition.loot, &mut rng) {
+            if entry.item_id != 0 && inventory.has_room_for(entry.item_id) {
+                inventory_events.send(InventoryUpdateEvent {
+                    item_id: entry.item_id,
+                    quantity: entry.quantity as i32,
+                });
+            }
+        }
+        award_thieving_experience(&mut commands, &mut skill_events, text_position, definition.experience);
+        println!("You pick the {}'s pocket.", npc.name.to_lowercase());
+        return;
+    }
+
+    let caught = Caught {
+        thief: player_entity,
+        thief_position: player_transform.translation,
+        catcher_position: npc_transform.translation,
+        shout: "What do you think you're doing?",
+        damage: definition.failure_damage,
+        stun_time: definition.stun_time,
+    };
+    get_caught(&mut commands, caught, &mut damage_events, &mut theft_events);
+    println!("You fail to pick the {}'s pocket and are stunned.", npc.name.to_lowercase());
+}
+
+// A failed theft: who was caught, by what, and how badly it goes for them
+struct Caught {
+    thief: Entity,
+    thief_position: Vec3,
+    catcher_position: Vec3,
+    shout: &'static str,
+    damage: u32,
+    stun_time: f32,
+}
+
+// Caught: the NPC or stall owner hits the player, they're stunned, and nearby guards hear about it
+fn get_caught(
+    commands: &mut Commands,
+    caught: Caught,
+    damage_events: &mut EventWriter<DamageEvent>,
+    theft_events: &mut EventWriter<TheftEvent>,
+) {
+    spawn_floating_text(
+        commands,
+        caught.catcher_position + Vec3::new(0.0, 2.0, 0.0),
+        caught.shout.to_string(),
+        Color::rgb(1.0, 1.0, 1.0),
+    );
+    damage_events.send(DamageEvent {
+        target: caught.thief,
+        amount: caught.damage,
+        is_player_source: false,
+        attacker: None,
+    });
+    commands.entity(caught.thief).insert(Stunned {
+        timer: Timer::from_seconds(caught.stun_time, TimerMode::Once),
+    });
+    theft_events.send(TheftEvent {
+        position: caught.thief_position,
+    });
+}
+
+// Award thieving XP with a floating text popup
+fn award_thieving_experience(
+    commands: &mut Commands,
+    skill_events: &mut EventWriter<SkillExperienceEvent>,
+    position: Vec3,
+    experience: u32,
+) {
+    skill_events.send(SkillExperienceEvent {
+        skill_name: "thieving".to_string(),
+        experience,
+    });
+    spawn_floating_text(commands, position, format!("+{} thieving XP", experience), Color::rgb(0.9, 0.9, 0.1));
+}
+
+// Guards that see a theft turn aggressive towards the thief
+fn alert_guards(
+    mut commands: Commands,
+    mut events: EventReader<TheftEvent>,
+    mut guard_query: Query<(Entity, &Transform, &Guard, &mut Enemy, &mut CombatState)>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    for event in events.read() {
+        for (entity, transform, guard, mut enemy, mut combat_state) in guard_query.iter_mut() {
+            if transform.translation.distance(event.position) > guard.sight_range {
+                continue;
+            }
+
+            // Guards go after thieves whatever their level
+            enemy.aggression_range = guard.chase_range;
+            combat_state.target = player_query.get_single().ok();
+            commands.entity(entity).insert(AlertedGuard {
+                timer: Timer::from_seconds(30.0, TimerMode::Once),
+            });
+            spawn_floating_text(
+                &mut commands,
+                transform.translation + Vec3::new(0.0, 2.0, 0.0),
+                "Stop, thief!".to_string(),
+                Color::rgb(1.0, 0.4, 0.4),
+            );
+        }
+    }
+}
+
+// Guards lose interest after a while and walk back to their post
+fn calm_guards(
+    mut commands: Commands,
+    mut guard_query: Query<(Entity, &mut Enemy, &mut CombatState, &mut AlertedGuard)>,
+    time: Res<Time>,
+) {
+    for (entity, mut enemy, mut combat_state, mut alerted) in guard_query.iter_mut() {
+        alerted.timer.tick(time.delta());
+
+        if alerted.timer.finished() {
+            enemy.aggression_range = 0.0;
+            combat_state.target = None;
+            commands.entity(entity).remove::<AlertedGuard>();
+        }
+    }
+}
+
+// Let the player move again once a stun wears off
+fn recover_from_stun(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Stunned)>,
+    time: Res<Time>,
+) {
+    for (entity, mut stunned) in query.iter_mut() {
+        stunned.timer.tick(time.delta());
+
+        if stunned.timer.finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+// Put goods back on empty stalls
+fn restock_stalls(
+    mut commands: Commands,
+    mut stall_query: Query<(Entity, &MarketStall, &mut EmptyStall)>,
+    mut goods_query: Query<&mut Visibility>,
+    time: Res<Time>,
+) {
+    for (entity, stall, mut empty) in stall_query.iter_mut() {
+        empty.timer.tick(time.delta());
+
+        if empty.timer.finished() {
+            commands.entity(entity).remove::<EmptyStall>();
+            if let Ok(mut visibility) = goods_query.get_mut(stall.goods) {
+                *visibility = Visibility::Inherited;
+            }
+        }
+    }
+}